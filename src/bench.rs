@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::cli::Options;
+use crate::table::format_table;
+use crate::{DayError, Solution};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+
+        assert!(!samples.is_empty(), "need at least one sample");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        }
+        else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = sorted.iter()
+            .map(|s| (s.as_secs_f64() - mean).powi(2))
+            .sum::<f64>() / n as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub day: u32,
+    pub setup: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+}
+
+impl DayBench {
+    pub fn phases(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("setup", &self.setup),
+            ("part_one", &self.part_one),
+            ("part_two", &self.part_two),
+        ]
+    }
+}
+
+fn time_runs<T>(warmup_runs: usize, runs: usize, mut f: impl FnMut() -> T) -> Stats {
+
+    for _ in 0..warmup_runs {
+        black_box(f());
+    }
+
+    let samples: Vec<Duration> = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(&samples)
+}
+
+// Time each stage on its own, so a slow parser doesn't hide a change in
+// either of the parts. Both parts are run once first, as there's no point
+// timing a part that can't find an answer.
+pub fn bench<S: Solution>(solution: &S, input: &str, warmup_runs: usize, runs: usize) -> Result<DayBench, DayError> {
+
+    let parsed = solution.parse(input)?;
+    solution.part_one(&parsed).map_err(|error| DayError::Solve { part: 1, error })?;
+    solution.part_two(&parsed).map_err(|error| DayError::Solve { part: 2, error })?;
+
+    Ok(DayBench {
+        day: S::DAY,
+        setup: time_runs(warmup_runs, runs, || solution.parse(black_box(input))),
+        part_one: time_runs(warmup_runs, runs, || solution.part_one(black_box(&parsed))),
+        part_two: time_runs(warmup_runs, runs, || solution.part_two(black_box(&parsed))),
+    })
+}
+
+// Baselines are written as a JSON array with one object per line, which
+// keeps them easy to diff.
+pub fn save_baseline(path: &Path, results: &[DayBench]) -> io::Result<()> {
+
+    let entries: Vec<String> = results.iter()
+        .flat_map(|result| {
+            result.phases().iter()
+                .map(|(phase, stats)| format!(
+                    "  {{\"day\": {}, \"phase\": \"{}\", \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"stddev_ns\": {}}}",
+                    result.day,
+                    phase,
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.mean.as_nanos(),
+                    stats.stddev.as_nanos(),
+                ))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    fs::write(path, format!("[\n{}\n]\n", entries.join(",\n")))
+}
+
+// Read the median time for each day and phase back out of a baseline. It
+// can be laid out however it likes as long as it's valid JSON, but one
+// without any entries is an error, since nothing could be compared.
+pub fn parse_baseline(text: &str) -> Result<HashMap<(u32, String), Duration>, String> {
+
+    let entries = match serde_json::from_str(text).map_err(|e| e.to_string())? {
+        Value::Array(entries) => entries,
+        _ => return Err("expected an array of entries".to_string()),
+    };
+
+    if entries.is_empty() {
+        return Err("there are no entries to compare against".to_string())
+    }
+
+    entries.iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let day = entry["day"].as_u64().and_then(|day| u32::try_from(day).ok());
+            let phase = entry["phase"].as_str();
+            let median = entry["median_ns"].as_u64();
+
+            match (day, phase, median) {
+                (Some(day), Some(phase), Some(median)) => Ok(((day, phase.to_string()), Duration::from_nanos(median))),
+                _ => Err(format!("entry {} needs a day, a phase and a median_ns", i + 1)),
+            }
+        })
+        .collect()
+}
+
+pub fn load_baseline(path: &Path) -> Result<HashMap<(u32, String), Duration>, String> {
+
+    let text = fs::read_to_string(path).map_err(|e| format!("could not read baseline {}: {}", path.display(), e))?;
+
+    parse_baseline(&text).map_err(|e| format!("bad baseline {}: {}", path.display(), e))
+}
+
+// How much slower (positive) or faster (negative) the current time is than
+// the baseline, as a percentage.
+pub fn percent_change(baseline: Duration, current: Duration) -> f64 {
+    (current.as_secs_f64() - baseline.as_secs_f64()) / baseline.as_secs_f64() * 100.0
+}
+
+// Print a table of results, comparing against and saving baselines as
+// asked. Returns whether anything regressed past the threshold.
+pub fn report(results: &[DayBench], options: &Options) -> Result<bool, String> {
+
+    let baseline = match &options.baseline {
+        Some(path) => Some(load_baseline(path)?),
+        None => None,
+    };
+
+    let mut regressed = false;
+    let mut rows = Vec::new();
+
+    for result in results {
+        for (phase, stats) in result.phases().iter() {
+
+            let change = baseline.as_ref()
+                .and_then(|b| b.get(&(result.day, phase.to_string())))
+                .map(|&before| {
+                    let change = percent_change(before, stats.median);
+                    if change > options.threshold {
+                        regressed = true;
+                        format!("{:+.1}% REGRESSION", change)
+                    }
+                    else {
+                        format!("{:+.1}%", change)
+                    }
+                });
+
+            rows.push(vec![
+                result.day.to_string(),
+                phase.to_string(),
+                format!("{:?}", stats.min),
+                format!("{:?}", stats.median),
+                format!("{:?}", stats.mean),
+                format!("{:?}", stats.stddev),
+                change.unwrap_or_else(|| "-".to_string()),
+            ]);
+        }
+    }
+
+    println!("{}", format_table(&["Day", "Phase", "Min", "Median", "Mean", "Std dev", "vs baseline"], &rows));
+
+    if let Some(path) = &options.save_baseline {
+        save_baseline(path, results)
+            .map_err(|e| format!("could not save baseline {}: {}", path.display(), e))?;
+        println!();
+        println!("Saved baseline to {}", path.display());
+    }
+
+    Ok(regressed)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::time::Duration;
+
+    use super::{load_baseline, parse_baseline, percent_change, save_baseline, DayBench, Stats};
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&millis(&[4, 2, 8, 6]));
+
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.stddev.as_micros(), 2236);
+
+        assert_eq!(Stats::from_samples(&millis(&[3, 1, 2])).median, Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_percent_change() {
+        assert_eq!(percent_change(Duration::from_millis(10), Duration::from_millis(12)), 20.0);
+        assert_eq!(percent_change(Duration::from_millis(10), Duration::from_millis(5)), -50.0);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let stats = Stats::from_samples(&millis(&[1, 2, 3]));
+        let results = vec![DayBench { day: 7, setup: stats, part_one: stats, part_two: stats }];
+
+        let path = env::temp_dir().join(format!("aoc-baseline-{}.json", std::process::id()));
+        save_baseline(&path, &results).unwrap();
+        let baseline = load_baseline(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(baseline.len(), 3);
+        assert_eq!(baseline[&(7, "part_two".to_string())], Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_parse_baseline_however_its_laid_out() {
+        let baseline = parse_baseline(r#"[{ "median_ns":2000,
+            "phase" : "setup",
+            "day":3, "note": "by hand" }]"#).unwrap();
+
+        assert_eq!(baseline[&(3, "setup".to_string())], Duration::from_micros(2));
+    }
+
+    #[test]
+    fn test_parse_baseline_errors() {
+        assert_eq!(parse_baseline("[]"), Err("there are no entries to compare against".to_string()));
+        assert_eq!(parse_baseline("{}"), Err("expected an array of entries".to_string()));
+        assert_eq!(parse_baseline(r#"[{"day": 1, "phase": "setup"}]"#), Err("entry 1 needs a day, a phase and a median_ns".to_string()));
+        assert!(parse_baseline("[{\"day\": 1,").is_err());
+    }
+}
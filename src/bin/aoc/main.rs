@@ -0,0 +1,219 @@
+use std::env;
+use std::process;
+
+use aoc::bench;
+use aoc::cli::{self, Options};
+use aoc::days;
+use aoc::output::{self, Format};
+use aoc::runner::{self, DayResult, PuzzleInput};
+use aoc::table::format_table;
+use aoc::verify;
+
+const USAGE: &str = "Usage: aoc run <DAYS>... [OPTIONS]
+       aoc verify [DAYS]... [OPTIONS]
+
+DAYS can be a single day (7), a range (1..=12 or 1..13), or 'all'.
+Inputs are read from <DIR>/aocNN/input.txt, where <DIR> defaults to the
+src/bin directory of this crate. verify checks every day by default,
+comparing against the answers recorded in <DIR>/aocNN/answers.toml.";
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    s.parse::<u32>().map_err(|_| format!("'{}' is not a valid day", s))
+}
+
+// Turn a day spec from the command line into the days it covers. Ranges
+// quietly skip days without solutions, but asking for one by name doesn't.
+fn parse_days(spec: &str) -> Result<Vec<u32>, String> {
+
+    if spec == "all" {
+        return Ok(days::IMPLEMENTED.to_vec())
+    }
+
+    let range = if let Some((start, end)) = spec.split_once("..=") {
+        parse_day(start)?..=parse_day(end)?
+    }
+    else if let Some((start, end)) = spec.split_once("..") {
+        parse_day(start)?..=parse_day(end)?.saturating_sub(1)
+    }
+    else {
+        let day = parse_day(spec)?;
+        if !days::IMPLEMENTED.contains(&day) {
+            return Err(format!("day {} has no solution yet", day))
+        }
+        return Ok(vec![day])
+    };
+
+    Ok(days::IMPLEMENTED.iter().copied().filter(|day| range.contains(day)).collect())
+}
+
+fn print_summary(results: &[DayResult]) {
+
+    let rows: Vec<Vec<String>> = results.iter()
+        .map(|r| vec![
+            r.day.to_string(),
+            r.part_one.clone(),
+            r.part_two.clone(),
+            format!("{:?}", r.timings.setup),
+            format!("{:?}", r.timings.part_one),
+            format!("{:?}", r.timings.part_two),
+            format!("{:?}", r.timings.total()),
+        ])
+        .collect();
+
+    println!("{}", format_table(&["Day", "Part 1", "Part 2", "Setup", "Part 1 time", "Part 2 time", "Total"], &rows));
+
+    let total = results.iter().map(|r| r.timings.total()).sum::<std::time::Duration>();
+    println!();
+    println!("Total time: {:?}", total);
+}
+
+fn read_input(options: &Options, day: u32) -> Result<PuzzleInput, String> {
+    PuzzleInput::read_path(&runner::input_path(&options.inputs_dir, day))
+}
+
+// Solve a day, writing its report to stderr so that stdout is left to the
+// results. parse_days only hands back days that have solutions, so the
+// unwrap is fine.
+fn solve_day(options: &Options, day: u32, input: &PuzzleInput) -> Result<DayResult, String> {
+
+    let (result, report) = days::solve(day, input, options).unwrap();
+
+    if let Ok(Some(report)) = &report {
+        eprintln!("== day {} ==", day);
+        eprintln!("{}", report);
+    }
+
+    let result = result.map_err(|e| runner::day_failed(day, &input.source, e))?;
+    report.map_err(|e| runner::day_failed(day, &input.source, e))?;
+
+    Ok(result)
+}
+
+fn parse_all_days(specs: &[String]) -> Result<Vec<u32>, String> {
+
+    let mut days = Vec::new();
+    for spec in specs {
+        days.extend(parse_days(spec)?);
+    }
+
+    if days.is_empty() {
+        return Err("no days to run".to_string())
+    }
+
+    Ok(days)
+}
+
+// Returns whether everything went well, as opposed to a benchmark having
+// regressed.
+fn run(args: &[String]) -> Result<bool, String> {
+
+    let (options, specs) = cli::parse_options(args)?;
+    let days = parse_all_days(&specs)?;
+
+    if options.stream {
+        return Err("run doesn't support --stream".to_string())
+    }
+
+    // parse_days only hands back days that have solutions, so the unwrap
+    // on benchmarking below is fine.
+
+    if let Some(runs) = options.bench_runs {
+        let mut results = Vec::new();
+        for &day in &days {
+            let input = read_input(&options, day)?;
+            let result = days::bench(day, &input.text, &options, runs).unwrap()
+                .map_err(|e| runner::day_failed(day, &input.source, e))?;
+            results.push(result);
+        }
+
+        return bench::report(&results, &options).map(|regressed| !regressed)
+    }
+
+    let mut results = Vec::new();
+    for &day in &days {
+        let input = read_input(&options, day)?;
+        results.push(solve_day(&options, day, &input)?);
+    }
+
+    match options.format {
+        Format::Json => println!("{}", output::to_json(&results)),
+        Format::Csv => println!("{}", output::to_csv(&results)),
+        Format::Text => print_summary(&results),
+    }
+
+    Ok(true)
+}
+
+// Returns whether every recorded answer matched.
+fn verify(args: &[String]) -> Result<bool, String> {
+
+    let (options, specs) = cli::parse_options(args)?;
+
+    if options.bench_runs.is_some() || options.format != Format::Text || options.stream {
+        return Err("verify doesn't support --bench, --format or --stream".to_string())
+    }
+
+    let days = match specs.len() {
+        0 => days::IMPLEMENTED.to_vec(),
+        _ => parse_all_days(&specs)?,
+    };
+
+    // A day that can't be checked is recorded as an error, and the rest of
+    // the days are still checked.
+    let verifications = days.iter()
+        .map(|&day| verify_day(&options, day).unwrap_or_else(|e| verify::failed(day, e)))
+        .collect::<Vec<_>>();
+
+    Ok(verify::report(&verifications))
+}
+
+fn verify_day(options: &Options, day: u32) -> Result<verify::DayVerification, String> {
+
+    let expected = verify::load_answers(&verify::answers_path(&options.inputs_dir, day))?;
+    let input = read_input(options, day)?;
+    let result = solve_day(options, day, &input)?;
+
+    Ok(verify::verify(&result, &expected))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let outcome = match args.get(1).map(String::as_str) {
+        Some("run") => run(&args[2..]),
+        Some("verify") => verify(&args[2..]),
+        _ => {
+            eprintln!("{}\n\n{}", USAGE, cli::OPTIONS_USAGE);
+            process::exit(2);
+        }
+    };
+
+    match outcome {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            process::exit(1);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::parse_days;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("7"), Ok(vec![7]));
+        assert_eq!(parse_days("1..=4"), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(parse_days("8..13"), Ok(vec![8, 9, 12]));
+        assert_eq!(parse_days("all").unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_parse_days_rejects_missing_days() {
+        assert!(parse_days("10").is_err());
+        assert!(parse_days("seven").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use crate::output::Format;
+use crate::runner;
+
+// Flags understood by both the aoc runner and each day's own binary. Any
+// other `--name value` is kept for the day itself to make sense of, and
+// anything that isn't a flag is handed back to the caller.
+#[derive(Debug)]
+pub struct Options {
+    pub inputs_dir: PathBuf,
+    pub format: Format,
+    pub bench_runs: Option<usize>,
+    pub warmup_runs: usize,
+    pub threshold: f64,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+    pub stream: bool,
+    pub day_options: Vec<(String, String)>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            inputs_dir: runner::default_inputs_dir(),
+            format: Format::Text,
+            bench_runs: None,
+            warmup_runs: 3,
+            threshold: 10.0,
+            baseline: None,
+            save_baseline: None,
+            stream: false,
+            day_options: Vec::new(),
+        }
+    }
+}
+
+pub const OPTIONS_USAGE: &str = "Options:
+    --inputs <DIR>          Read inputs from <DIR>/aocNN/input.txt
+    --format <FORMAT>       Print answers as text, json or csv (default text)
+    --bench <N>             Time each stage N times and report statistics
+    --warmup <N>            Untimed runs before benchmarking (default 3)
+    --baseline <FILE>       Compare benchmark medians against a saved baseline
+    --threshold <PERCENT>   Slowdown that counts as a regression (default 10)
+    --save-baseline <FILE>  Save benchmark results as a new baseline
+    --stream                Solve while reading the input, for days that can
+    --<NAME> <VALUE>        Set an option of the day's own, e.g. --k 4 for day 1";
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value.parse::<T>().map_err(|_| format!("invalid value '{}' for {}", value, flag))
+}
+
+fn parse_format(value: Option<&String>) -> Result<Format, String> {
+    value.ok_or("--format needs a value")?.parse::<Format>()
+}
+
+// A range of numbers given as 'a..=b', 'a..b' or just 'a'.
+pub fn parse_range(value: &str) -> Result<RangeInclusive<usize>, String> {
+
+    let number = |s: &str| s.parse::<usize>().map_err(|_| format!("'{}' is not a valid number in '{}'", s, value));
+
+    let range = if let Some((start, end)) = value.split_once("..=") {
+        number(start)?..=number(end)?
+    }
+    else if let Some((start, end)) = value.split_once("..") {
+        let end = number(end)?.checked_sub(1).ok_or_else(|| format!("'{}' is an empty range", value))?;
+        number(start)?..=end
+    }
+    else {
+        let n = number(value)?;
+        n..=n
+    };
+
+    if range.is_empty() {
+        return Err(format!("'{}' is an empty range", value))
+    }
+
+    Ok(range)
+}
+
+pub fn parse_options(args: &[String]) -> Result<(Options, Vec<String>), String> {
+
+    let mut options = Options::default();
+    let mut rest = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--inputs" => options.inputs_dir = parse_value(arg, args.next())?,
+            "--format" => options.format = parse_format(args.next())?,
+            "--bench" => options.bench_runs = Some(parse_value(arg, args.next())?),
+            "--warmup" => options.warmup_runs = parse_value(arg, args.next())?,
+            "--threshold" => options.threshold = parse_value(arg, args.next())?,
+            "--baseline" => options.baseline = Some(parse_value(arg, args.next())?),
+            "--save-baseline" => options.save_baseline = Some(parse_value(arg, args.next())?),
+            "--stream" => options.stream = true,
+            flag if flag.starts_with("--") => {
+                let value = args.next().ok_or_else(|| format!("{} needs a value", flag))?;
+                options.day_options.push((flag[2..].to_string(), value.clone()));
+            }
+            _ => rest.push(arg.clone()),
+        }
+    }
+
+    if options.bench_runs == Some(0) {
+        return Err("--bench needs at least one run".to_string())
+    }
+
+    if options.bench_runs.is_some() && options.format != Format::Text {
+        return Err("benchmark results can only be shown as text".to_string())
+    }
+
+    if options.stream && options.bench_runs.is_some() {
+        return Err("--stream can't be used along with --bench".to_string())
+    }
+
+    // Reports need the whole input, so nothing would be given for them.
+    let report = options.day_options.iter().find(|(name, _)| name == "report" || name == "render");
+    if let (true, Some((name, _))) = (options.stream, report) {
+        return Err(format!("--stream can't be used along with --{}", name))
+    }
+
+    if options.bench_runs.is_none() && (options.baseline.is_some() || options.save_baseline.is_some()) {
+        return Err("baselines can only be used along with --bench".to_string())
+    }
+
+    Ok((options, rest))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_options, parse_range};
+    use crate::output::Format;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let (options, rest) = parse_options(&args("1..=3 --bench 20 --threshold 5.5 all")).unwrap();
+
+        assert_eq!(rest, args("1..=3 all"));
+        assert_eq!(options.bench_runs, Some(20));
+        assert_eq!(options.warmup_runs, 3);
+        assert_eq!(options.threshold, 5.5);
+        assert_eq!(options.format, Format::Text);
+
+        let (options, _) = parse_options(&args("--format csv")).unwrap();
+        assert_eq!(options.format, Format::Csv);
+        assert!(!options.stream);
+
+        let (options, rest) = parse_options(&args("--stream - --k 3")).unwrap();
+        assert!(options.stream);
+        assert_eq!(rest, args("-"));
+
+        let (options, rest) = parse_options(&args("--k 4 input.txt --target 100")).unwrap();
+        assert_eq!(rest, args("input.txt"));
+        assert_eq!(options.day_options, vec![
+            ("k".to_string(), "4".to_string()),
+            ("target".to_string(), "100".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_options_errors() {
+        assert!(parse_options(&args("--bench")).is_err());
+        assert!(parse_options(&args("--bench ten")).is_err());
+        assert!(parse_options(&args("--bench 0")).is_err());
+        assert!(parse_options(&args("--k")).is_err());
+        assert!(parse_options(&args("--baseline old.json")).is_err());
+        assert!(parse_options(&args("--format xml")).is_err());
+        assert!(parse_options(&args("--format json --bench 10")).is_err());
+        assert!(parse_options(&args("--stream --bench 10")).is_err());
+        assert_eq!(parse_options(&args("--stream --report summary")).unwrap_err(), "--stream can't be used along with --report");
+        assert!(parse_options(&args("--render ascii --stream")).is_err());
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("1..=20"), Ok(1..=20));
+        assert_eq!(parse_range("1..6"), Ok(1..=5));
+        assert_eq!(parse_range("3"), Ok(3..=3));
+        assert!(parse_range("5..=3").is_err());
+        assert!(parse_range("0..0").is_err());
+        assert!(parse_range("a..=3").is_err());
+    }
+}
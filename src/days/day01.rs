@@ -0,0 +1,523 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{parse_lines, LineError};
+use crate::{ParseError, SolveError, Solution};
+
+fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    parse_lines(input, |line| {
+        let value = line.trim();
+        value.parse::<i64>().map_err(|e| {
+            LineError::new(line.find(value).unwrap(), format!("'{}' is not a valid expense: {}", value, e))
+        })
+    })
+}
+
+// What to give back for each part: the product of the first group found,
+// or how many groups there are. All counts them too, and also lists every
+// group in the report.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Mode {
+    First,
+    Count,
+    All,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "first" => Ok(Mode::First),
+            "count" => Ok(Mode::Count),
+            "all" => Ok(Mode::All),
+            unknown => Err(format!("unknown mode '{}', expected first, count or all", unknown)),
+        }
+    }
+}
+
+// A group of expenses that add up to the target, as (index, value) pairs
+// in input order.
+pub type Group = Vec<(usize, i64)>;
+
+fn format_groups(groups: &[Group]) -> String {
+
+    if groups.is_empty() {
+        return "none".to_string()
+    }
+
+    groups.iter()
+        .map(|group| group.iter()
+            .map(|(index, value)| format!("{} (entry {})", value, index + 1))
+            .collect::<Vec<_>>()
+            .join(" + "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Answer {
+    Product(i64),
+    Count(u64),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Product(product) => write!(f, "{}", product),
+            Answer::Count(count) => write!(f, "{}", count),
+        }
+    }
+}
+
+// Part one looks for k expenses that add up to the target, and part two
+// for one more than that. The puzzle itself wants pairs and triples that
+// add up to 2020.
+pub struct DayOne {
+    pub k: usize,
+    pub target: i64,
+    pub mode: Mode,
+}
+
+impl Default for DayOne {
+    fn default() -> Self {
+        DayOne {
+            k: 2,
+            target: 2020,
+            mode: Mode::First,
+        }
+    }
+}
+
+impl DayOne {
+    fn answer(&self, values: &[i64], k: usize) -> Result<Answer, SolveError> {
+        match self.mode {
+            Mode::First => product_of_k_sum(values, k, self.target).map(Answer::Product),
+            Mode::Count => count_k_sums(values, k, self.target)
+                .map(Answer::Count)
+                .ok_or_else(|| SolveError::new(format!("too many groups of {} add up to {} to count", k, self.target))),
+            Mode::All => Ok(Answer::Count(KSumCombinations::new(values, k, self.target).count() as u64)),
+        }
+    }
+
+    fn groups(&self, values: &[i64], k: usize) -> Vec<Group> {
+        KSumCombinations::new(values, k, self.target)
+            .map(|indices| indices.into_iter().map(|i| (i, values[i])).collect())
+            .collect()
+    }
+}
+
+impl Solution for DayOne {
+    const DAY: u32 = 1;
+
+    type Input = Vec<i64>;
+    type Output = Answer;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        self.answer(input, self.k)
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        self.answer(input, self.k + 1)
+    }
+
+    fn report(&self, input: &Self::Input) -> Result<Option<String>, SolveError> {
+
+        if self.mode != Mode::All {
+            return Ok(None)
+        }
+
+        let sections: Vec<String> = [("one", self.k), ("two", self.k + 1)].iter()
+            .map(|&(part, k)| format!("part {} ({} entries adding up to {}):\n{}", part, k, self.target, format_groups(&self.groups(input, k))))
+            .collect();
+
+        Ok(Some(sections.join("\n\n")))
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "k" => match value.parse::<usize>() {
+                Ok(k) if k > 0 => self.k = k,
+                _ => return Err(format!("--k should be a positive number, not '{}'", value)),
+            },
+            "target" => {
+                self.target = value.parse::<i64>()
+                    .map_err(|_| format!("--target should be a number, not '{}'", value))?;
+            }
+            "mode" => self.mode = value.parse::<Mode>()?,
+            _ => return Err(format!("day 1 has no option --{}", name)),
+        }
+        Ok(())
+    }
+}
+
+fn product_of_k_sum(values: &[i64], k: usize, target: i64) -> Result<i64, SolveError> {
+
+    let parts = find_k_sum(values, k, target)
+        .ok_or_else(|| SolveError::new(format!("no {} entries add up to {}", k, target)))?;
+
+    parts.iter()
+        .try_fold(1i64, |product, &part| product.checked_mul(part))
+        .ok_or_else(|| SolveError::new(format!("the product of {:?} is too big", parts)))
+}
+
+// Find k of the values that add up to the target, smallest first. Each
+// value can only be used once, though it may appear more than once. Sums
+// are worked out as i128s, since adding up a few i64s can overflow one.
+pub fn find_k_sum(values: &[i64], k: usize, target: i64) -> Option<Vec<i64>> {
+
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+
+    find_k_sum_sorted(&sorted, k, target as i128)
+}
+
+fn find_k_sum_sorted(sorted: &[i64], k: usize, target: i128) -> Option<Vec<i64>> {
+
+    match k {
+        0 => return if target == 0 { Some(Vec::new()) } else { None },
+        1 => {
+            let target = i64::try_from(target).ok()?;
+            return sorted.binary_search(&target).ok().map(|i| vec![sorted[i]])
+        }
+        2 => return find_pair_sum(sorted, target).map(|(low, high)| vec![low, high]),
+        _ => {}
+    }
+
+    // Fix the smallest value and look for the other k - 1 among the values
+    // after it. Trying the same value twice would only find the same sums.
+    for (i, &low) in sorted.iter().enumerate() {
+
+        if i > 0 && sorted[i - 1] == low {
+            continue;
+        }
+
+        if let Some(mut rest) = find_k_sum_sorted(&sorted[i + 1..], k - 1, target - low as i128) {
+            rest.insert(0, low);
+            return Some(rest);
+        }
+    }
+
+    None
+}
+
+fn find_pair_sum(sorted: &[i64], total: i128) -> Option<(i64, i64)> {
+
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let mut low_index = 0;
+    let mut high_index = sorted.len() - 1;
+
+    while low_index < high_index {
+
+        let low = sorted[low_index];
+        let high = sorted[high_index];
+        let sum = low as i128 + high as i128;
+
+        match sum.cmp(&total) {
+            Ordering::Less => { low_index += 1; }
+            Ordering::Greater => { high_index -= 1; }
+            Ordering::Equal => return Some((low, high)),
+        }
+    }
+
+    None
+}
+
+// Every group of k entries that add up to the target, as indices into the
+// values in increasing order. Entries are told apart by index, so equal
+// values still make separate groups.
+//
+// The search walks the values in sorted order, and gives up on a branch as
+// soon as the smallest or largest values left can't reach the target.
+pub struct KSumCombinations {
+    // (index, value) pairs sorted by value
+    sorted: Vec<(usize, i64)>,
+    // prefix[i] is the sum of the first i sorted values, as an i128 like
+    // the running sum so that neither can overflow.
+    prefix: Vec<i128>,
+    k: usize,
+    target: i64,
+    // positions in sorted of the entries picked so far
+    stack: Vec<usize>,
+    sum: i128,
+    started: bool,
+}
+
+impl KSumCombinations {
+    pub fn new(values: &[i64], k: usize, target: i64) -> Self {
+
+        let mut sorted: Vec<(usize, i64)> = values.iter().copied().enumerate().collect();
+        sorted.sort_unstable_by_key(|&(index, value)| (value, index));
+
+        let mut prefix = vec![0];
+        for (_, value) in &sorted {
+            prefix.push(prefix.last().unwrap() + *value as i128);
+        }
+
+        KSumCombinations {
+            sorted,
+            prefix,
+            k,
+            target,
+            stack: Vec::with_capacity(k),
+            sum: 0,
+            started: false,
+        }
+    }
+
+    // Pick the next entry at position from or later that could still make
+    // up the target, given how many more entries are needed after it.
+    fn push_from(&mut self, from: usize) -> bool {
+
+        let n = self.sorted.len();
+        let after = self.k - self.stack.len() - 1;
+
+        for position in from..n {
+            if position + after >= n {
+                break;
+            }
+
+            let sum = self.sum + self.sorted[position].1 as i128;
+            let smallest = sum + self.prefix[position + 1 + after] - self.prefix[position + 1];
+            if smallest > self.target as i128 {
+                break;
+            }
+            let largest = sum + self.prefix[n] - self.prefix[n - after];
+            if largest < self.target as i128 {
+                continue;
+            }
+
+            self.stack.push(position);
+            self.sum = sum;
+            return true
+        }
+
+        false
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        let position = self.stack.pop()?;
+        self.sum -= self.sorted[position].1 as i128;
+        Some(position)
+    }
+
+    // Fill the stack up to k entries, backtracking as needed. With no
+    // entries left to pick the bounds in push_from only let through exact
+    // sums, so a full stack is always a match.
+    fn fill_from(&mut self, mut from: usize) -> bool {
+
+        while self.stack.len() < self.k {
+            if self.push_from(from) {
+                from = self.stack.last().unwrap() + 1;
+            }
+            else {
+                match self.pop() {
+                    Some(position) => from = position + 1,
+                    None => return false,
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl Iterator for KSumCombinations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+
+        let found = if !self.started {
+            self.started = true;
+            (self.k > 0 || self.target == 0) && self.fill_from(0)
+        }
+        else {
+            match self.pop() {
+                Some(position) => self.fill_from(position + 1),
+                None => false,
+            }
+        };
+
+        if !found {
+            return None
+        }
+
+        let mut indices: Vec<usize> = self.stack.iter().map(|&position| self.sorted[position].0).collect();
+        indices.sort_unstable();
+
+        Some(indices)
+    }
+}
+
+// How many groups of k entries add up to the target, without listing them.
+// counts[j] maps each sum of j entries seen so far to how many ways there
+// are to make it. None means there are more groups than a u64 can count.
+pub fn count_k_sums(values: &[i64], k: usize, target: i64) -> Option<u64> {
+
+    let mut counts: Vec<HashMap<i128, u64>> = vec![HashMap::new(); k + 1];
+    counts[0].insert(0, 1);
+
+    for &value in values {
+        // Going down means each value only joins groups made without it.
+        for j in (1..=k).rev() {
+            let (fewer, more) = counts.split_at_mut(j);
+            for (&sum, &ways) in fewer[j - 1].iter() {
+                let count = more[0].entry(sum + value as i128).or_insert(0);
+                *count = count.checked_add(ways)?;
+            }
+        }
+    }
+
+    Some(counts[k].get(&(target as i128)).copied().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_k_sums, find_k_sum, KSumCombinations};
+    use super::{Answer, DayOne, Mode};
+    use crate::Solution;
+
+    #[test]
+    fn test_day_one_part_one() {
+        let example_values: Vec<i64> = vec![
+            1721,
+            979,
+            366,
+            299,
+            675,
+            1456,
+        ];
+
+        assert_eq!(DayOne::default().part_one(&example_values), Ok(Answer::Product(514579)));
+    }
+
+    #[test]
+    fn test_day_one_part_two() {
+        let example_values: Vec<i64> = vec![
+            1721,
+            979,
+            366,
+            299,
+            675,
+            1456,
+        ];
+
+        assert_eq!(DayOne::default().part_two(&example_values), Ok(Answer::Product(241861950)));
+    }
+
+    #[test]
+    fn test_find_k_sum() {
+        let values = vec![1721, 979, 366, 299, 675, 1456];
+
+        assert_eq!(find_k_sum(&values, 1, 675), Some(vec![675]));
+        assert_eq!(find_k_sum(&values, 2, 2020), Some(vec![299, 1721]));
+        assert_eq!(find_k_sum(&values, 3, 2020), Some(vec![366, 675, 979]));
+        assert_eq!(find_k_sum(&values, 4, 299 + 366 + 675 + 1456), Some(vec![299, 366, 675, 1456]));
+        assert_eq!(find_k_sum(&values, 2, 1), None);
+        assert_eq!(find_k_sum(&values, 7, 5496), None);
+    }
+
+    #[test]
+    fn test_find_k_sum_uses_each_entry_once() {
+        assert_eq!(find_k_sum(&[1010, 5], 2, 2020), None);
+        assert_eq!(find_k_sum(&[1010, 5, 1010], 2, 2020), Some(vec![1010, 1010]));
+        assert_eq!(find_k_sum(&[-5, 3, 3, 10], 3, 1), Some(vec![-5, 3, 3]));
+    }
+
+    #[test]
+    fn test_options() {
+        let mut day = DayOne::default();
+        day.set_option("k", "3").unwrap();
+        day.set_option("target", "2020").unwrap();
+
+        let example_values: Vec<i64> = vec![1721, 979, 366, 299, 675, 1456];
+        assert_eq!(day.part_one(&example_values), Ok(Answer::Product(241861950)));
+        assert!(day.part_two(&example_values).is_err());
+
+        assert!(day.set_option("k", "0").is_err());
+        assert!(day.set_option("size", "2").is_err());
+
+        day.set_option("mode", "count").unwrap();
+        assert_eq!(day.part_one(&example_values), Ok(Answer::Count(1)));
+        assert!(day.set_option("mode", "some").is_err());
+    }
+
+    #[test]
+    fn test_k_sum_combinations() {
+        let values = vec![1721, 979, 366, 299, 675, 1456];
+
+        let pairs: Vec<Vec<usize>> = KSumCombinations::new(&values, 2, 2020).collect();
+        assert_eq!(pairs, vec![vec![0, 3]]);
+
+        let groups: Vec<Vec<usize>> = KSumCombinations::new(&[1, 2, 3, 4, 5], 2, 6).collect();
+        assert_eq!(groups, vec![vec![0, 4], vec![1, 3]]);
+
+        assert_eq!(KSumCombinations::new(&values, 0, 0).count(), 1);
+        assert_eq!(KSumCombinations::new(&values, 7, 5496).count(), 0);
+    }
+
+    #[test]
+    fn test_k_sum_combinations_with_duplicates() {
+        let values = vec![1010, 1010, -3, 1010, 3];
+
+        let pairs: Vec<Vec<usize>> = KSumCombinations::new(&values, 2, 2020).collect();
+        assert_eq!(pairs, vec![vec![0, 1], vec![0, 3], vec![1, 3]]);
+        assert_eq!(KSumCombinations::new(&values, 4, 2020).count(), 3);
+    }
+
+    #[test]
+    fn test_count_k_sums_matches_enumeration() {
+        let values = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, -4, 0, 5];
+
+        for k in 0..=5 {
+            for target in -5..=30 {
+                assert_eq!(
+                    count_k_sums(&values, k, target),
+                    Some(KSumCombinations::new(&values, k, target).count() as u64),
+                    "k = {}, target = {}", k, target,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_mode() {
+        let day = DayOne { mode: Mode::All, ..DayOne::default() };
+        let values = vec![1721, 979, 366, 299, 675, 1456];
+
+        assert_eq!(day.part_one(&values), Ok(Answer::Count(1)));
+        assert_eq!(day.report(&values).unwrap().unwrap(), "part one (2 entries adding up to 2020):
+1721 (entry 1) + 299 (entry 4)
+
+part two (3 entries adding up to 2020):
+979 (entry 2) + 366 (entry 3) + 675 (entry 5)");
+
+        let day = DayOne { target: 3, ..day };
+        assert!(day.report(&values).unwrap().unwrap().ends_with("):\nnone"));
+    }
+
+    #[test]
+    fn test_sums_past_i64() {
+        let values = vec![i64::MAX, 1, 5, i64::MIN, -1];
+
+        assert!(DayOne::default().part_one(&values).is_err());
+        assert_eq!(DayOne { mode: Mode::Count, ..DayOne::default() }.part_two(&values), Ok(Answer::Count(0)));
+        assert_eq!(DayOne { mode: Mode::All, ..DayOne::default() }.part_one(&values), Ok(Answer::Count(0)));
+
+        assert_eq!(find_k_sum(&values, 2, -1), Some(vec![i64::MIN, i64::MAX]));
+        assert_eq!(find_k_sum(&values, 3, 0), Some(vec![i64::MIN, 1, i64::MAX]));
+        assert_eq!(find_k_sum(&values, 2, i64::MAX), None);
+        assert_eq!(count_k_sums(&values, 3, 0), Some(1));
+        assert_eq!(KSumCombinations::new(&values, 3, 0).collect::<Vec<_>>(), vec![vec![0, 1, 3]]);
+    }
+}
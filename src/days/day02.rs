@@ -0,0 +1,277 @@
+use std::fmt;
+use std::str::FromStr;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::{parse_numbered_lines, LineError};
+use crate::{ParseError, SolveError, Solution};
+
+pub mod policy;
+
+use policy::{LetterCount, LetterPosition, PasswordPolicy, Violation};
+
+fn parse_input(input: &str) -> Result<Vec<DatabasePassword>, ParseError> {
+    let entries = parse_numbered_lines(input, parse_line_as_password)?;
+
+    Ok(entries.into_iter()
+        .map(|(line, entry)| DatabasePassword { line, ..entry })
+        .collect())
+}
+
+fn parse_line_as_password(line: &str) -> Result<DatabasePassword, LineError> {
+    let (policy, password) = line.split_once(':')
+        .ok_or_else(|| LineError::new(0, "expected a policy and a password separated by ':'"))?;
+
+    Ok(DatabasePassword {
+        line: 0,
+        policy: parse_str_as_policy(policy)?,
+        password: password.trim().to_string(),
+    })
+}
+
+fn parse_str_as_policy(s: &str) -> Result<Policy, LineError> {
+    let (minmax, letter) = s.split_once(' ')
+        .ok_or_else(|| LineError::at(s, s, "expected a policy like '1-3 a'"))?;
+
+    // The letter can be anything that reads as a single character, even if
+    // it takes more than one code point to write.
+    let mut policy_letters = letter.graphemes(true);
+    let policy_letter = match (policy_letters.next(), policy_letters.next()) {
+        (Some(g), None) if !g.trim().is_empty() => g.to_string(),
+        _ => return Err(LineError::at(s, letter, format!("invalid policy letter '{}'", letter))),
+    };
+
+    let (min, max) = minmax.split_once('-')
+        .ok_or_else(|| LineError::at(s, minmax, "expected a policy range like '1-3'"))?;
+
+    let min = min.parse::<usize>()
+        .map_err(|e| LineError::at(s, min, format!("failed to parse policy minimum '{}': {}", min, e)))?;
+    let max = max.parse::<usize>()
+        .map_err(|e| LineError::at(s, max, format!("failed to parse policy maximum '{}': {}", max, e)))?;
+
+    Ok(Policy {
+        min,
+        max,
+        letter: policy_letter,
+    })
+}
+
+#[derive(Debug)]
+struct Policy {
+    min: usize,
+    max: usize,
+    letter: String,
+}
+
+// line is where the entry was in the input, counting from 1.
+#[derive(Debug)]
+pub struct DatabasePassword {
+    line: usize,
+    password: std::string::String,
+    policy: Policy,
+}
+
+impl fmt::Display for DatabasePassword {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{} {}: {}", self.policy.min, self.policy.max, self.policy.letter, self.password)
+    }
+}
+
+// Whether to report the entries checked by each part, and if so whether
+// to leave out the ones that passed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ReportMode {
+    Off,
+    Invalid,
+    All,
+}
+
+impl FromStr for ReportMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(ReportMode::Off),
+            "invalid" => Ok(ReportMode::Invalid),
+            "all" => Ok(ReportMode::All),
+            unknown => Err(format!("unknown report '{}', expected off, invalid or all", unknown)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EntryReport {
+    pub line: usize,
+    pub entry: String,
+    pub result: Result<(), Violation>,
+}
+
+impl fmt::Display for EntryReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.result {
+            Ok(()) => write!(f, "line {}: {}: ok", self.line, self.entry),
+            Err(violation) => write!(f, "line {}: {}: fails {}", self.line, self.entry, violation),
+        }
+    }
+}
+
+// Which policy each part checks the passwords against. The puzzle uses the
+// letter count for part one and the letter positions for part two, but
+// either can be swapped for another with --part-one or --part-two.
+pub struct DayTwo {
+    pub part_one_policy: Box<dyn PasswordPolicy>,
+    pub part_two_policy: Box<dyn PasswordPolicy>,
+    pub report: ReportMode,
+}
+
+impl Default for DayTwo {
+    fn default() -> Self {
+        DayTwo {
+            part_one_policy: Box::new(LetterCount),
+            part_two_policy: Box::new(LetterPosition),
+            report: ReportMode::Off,
+        }
+    }
+}
+
+impl DayTwo {
+    fn report_part(&self, part: &str, passwords: &[DatabasePassword], policy: &dyn PasswordPolicy) -> String {
+
+        let entries: Vec<EntryReport> = passwords.iter()
+            .map(|p| EntryReport { line: p.line, entry: p.to_string(), result: policy.check(p) })
+            .collect();
+
+        let valid = entries.iter().filter(|report| report.result.is_ok()).count();
+
+        let mut lines = vec![format!("part {}: {} of {} valid", part, valid, entries.len())];
+        lines.extend(entries.iter()
+            .filter(|report| self.report == ReportMode::All || report.result.is_err())
+            .map(|report| report.to_string()));
+
+        lines.join("\n")
+    }
+}
+
+impl Solution for DayTwo {
+    const DAY: u32 = 2;
+
+    type Input = Vec<DatabasePassword>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(count_valid(input, self.part_one_policy.as_ref()))
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(count_valid(input, self.part_two_policy.as_ref()))
+    }
+
+    fn report(&self, input: &Self::Input) -> Result<Option<String>, SolveError> {
+
+        if self.report == ReportMode::Off {
+            return Ok(None)
+        }
+
+        Ok(Some(format!("{}\n\n{}",
+            self.report_part("one", input, self.part_one_policy.as_ref()),
+            self.report_part("two", input, self.part_two_policy.as_ref()))))
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+
+        let policy = || policy::parse_policy(value).map_err(|e| format!("--{}: {}", name, e));
+
+        match name {
+            "part-one" => self.part_one_policy = policy()?,
+            "part-two" => self.part_two_policy = policy()?,
+            "report" => self.report = value.parse::<ReportMode>()?,
+            _ => return Err(format!("day 2 has no option --{}", name)),
+        }
+        Ok(())
+    }
+}
+
+fn count_valid(passwords: &[DatabasePassword], policy: &dyn PasswordPolicy) -> usize {
+
+    passwords.iter().filter(|p| policy.is_valid(p)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_input;
+    use super::{DayTwo, ReportMode};
+    use crate::Solution;
+
+    #[test]
+    fn test_parse_error_location() {
+        let error = parse_input("1-3 a: abcde\n1-x b: cdefg").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = parse_input("1-3 a: abcde\n1-3 bb: cdefg").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+
+    #[test]
+    fn test_day_two_part_one() {
+        let example_input =
+"1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+        let db = parse_input(example_input).unwrap();
+        assert_eq!(DayTwo::default().part_one(&db), Ok(2));
+    }
+
+    #[test]
+    fn test_day_two_part_two() {
+        let example_input =
+"1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+        let db = parse_input(example_input).unwrap();
+        assert_eq!(DayTwo::default().part_two(&db), Ok(1));
+    }
+
+    #[test]
+    fn test_policy_options() {
+        let db = parse_input("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+
+        let mut day = DayTwo::default();
+        day.set_option("part-one", "count AND NOT position").unwrap();
+        day.set_option("part-two", "no-runs=4").unwrap();
+        assert_eq!(day.part_one(&db), Ok(1));
+        assert_eq!(day.part_two(&db), Ok(2));
+
+        assert!(day.set_option("part-one", "count AND").is_err());
+        assert!(day.set_option("policy", "count").is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let db = parse_input("1-3 a: abcde\n\n1-3 b: cdefg\n2-10 c: ccccccccc").unwrap();
+
+        assert_eq!(DayTwo::default().report(&db), Ok(None));
+
+        let day = DayTwo { report: ReportMode::Invalid, ..DayTwo::default() };
+        assert_eq!(day.part_two(&db), Ok(1));
+        assert_eq!(day.report(&db).unwrap().unwrap(), "part one: 2 of 3 valid
+line 3: 1-3 b: cdefg: fails count: letter 'b' appears 0 times, needs 1..=3
+
+part two: 1 of 3 valid
+line 3: 1-3 b: cdefg: fails position: letter 'b' is at neither position 1 nor 3, needs exactly one
+line 4: 2-10 c: ccccccccc: fails position: position 10 is out of range for a 9-char password");
+
+        let day = DayTwo { report: ReportMode::All, ..DayTwo::default() };
+        assert!(day.report(&db).unwrap().unwrap().starts_with("part one: 2 of 3 valid
+line 1: 1-3 a: abcde: ok
+line 3: 1-3 b: cdefg: fails count: letter 'b' appears 0 times, needs 1..=3
+line 4: 2-10 c: ccccccccc: ok
+
+part two: 1 of 3 valid"));
+    }
+}
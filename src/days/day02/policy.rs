@@ -0,0 +1,628 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::DatabasePassword;
+
+// Which rule a password broke and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub rule: String,
+    pub reason: String,
+}
+
+impl Violation {
+    pub fn new(rule: impl Into<String>, reason: impl Into<String>) -> Self {
+        Violation {
+            rule: rule.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.reason)
+    }
+}
+
+// A rule a password can be checked against. Some rules use the policy that
+// came with the password in the database, and some bring their own.
+pub trait PasswordPolicy {
+    // The rule as it would be written on the command line.
+    fn name(&self) -> String;
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation>;
+
+    // Rules that get run over whole password dumps can skip building the
+    // Violation when all that's wanted is a yes or no.
+    fn is_valid(&self, entry: &DatabasePassword) -> bool {
+        self.check(entry).is_ok()
+    }
+}
+
+// Letters are grapheme clusters, so "é" is one letter whether it's written
+// as one code point or two. Most passwords are ASCII though, where every
+// byte is a letter of its own (bar "\r\n", which can't be inside a line),
+// so those are checked a byte at a time without splitting or allocating.
+pub fn count_letter(password: &str, letter: &str) -> usize {
+
+    if password.is_ascii() && letter.len() == 1 {
+        let byte = letter.as_bytes()[0];
+        return password.bytes().filter(|&b| b == byte).count()
+    }
+
+    password.graphemes(true).filter(|&g| g == letter).count()
+}
+
+pub fn letter_at(password: &str, index: usize) -> Option<&str> {
+
+    if password.is_ascii() {
+        return password.get(index..index + 1)
+    }
+
+    password.graphemes(true).nth(index)
+}
+
+pub fn letter_count(password: &str) -> usize {
+
+    if password.is_ascii() {
+        return password.len()
+    }
+
+    password.graphemes(true).count()
+}
+
+// The sled rental rule from part one: the policy letter appears between min
+// and max times.
+pub struct LetterCount;
+
+impl PasswordPolicy for LetterCount {
+    fn name(&self) -> String {
+        "count".to_string()
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+
+        let policy = &entry.policy;
+        let matching_chars = count_letter(&entry.password, &policy.letter);
+
+        if matching_chars < policy.min || matching_chars > policy.max {
+            return Err(Violation::new(self.name(), format!(
+                "letter '{}' appears {} {}, needs {}..={}",
+                policy.letter, matching_chars, times(matching_chars), policy.min, policy.max,
+            )))
+        }
+
+        Ok(())
+    }
+
+    fn is_valid(&self, entry: &DatabasePassword) -> bool {
+        let matching_chars = count_letter(&entry.password, &entry.policy.letter);
+        matching_chars >= entry.policy.min && matching_chars <= entry.policy.max
+    }
+}
+
+fn times(n: usize) -> &'static str {
+    if n == 1 { "time" } else { "times" }
+}
+
+// The toboggan rule from part two: the policy letter is at exactly one of
+// the two (1-based) positions.
+pub struct LetterPosition;
+
+impl PasswordPolicy for LetterPosition {
+    fn name(&self) -> String {
+        "position".to_string()
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+
+        let policy = &entry.policy;
+
+        let is_letter_at = |position: usize| {
+            match position.checked_sub(1).and_then(|index| letter_at(&entry.password, index)) {
+                Some(letter) => Ok(letter == policy.letter),
+                None => Err(Violation::new(self.name(), format!(
+                    "position {} is out of range for a {}-char password", position, letter_count(&entry.password),
+                ))),
+            }
+        };
+
+        match (is_letter_at(policy.min)?, is_letter_at(policy.max)?) {
+            (true, false) | (false, true) => Ok(()),
+            (true, true) => Err(Violation::new(self.name(), format!(
+                "letter '{}' is at both positions {} and {}, needs exactly one", policy.letter, policy.min, policy.max,
+            ))),
+            (false, false) => Err(Violation::new(self.name(), format!(
+                "letter '{}' is at neither position {} nor {}, needs exactly one", policy.letter, policy.min, policy.max,
+            ))),
+        }
+    }
+
+    fn is_valid(&self, entry: &DatabasePassword) -> bool {
+
+        let letter_at_position = |position: usize| {
+            position.checked_sub(1).and_then(|index| letter_at(&entry.password, index))
+        };
+
+        match (letter_at_position(entry.policy.min), letter_at_position(entry.policy.max)) {
+            (Some(first), Some(second)) => (first == entry.policy.letter) ^ (second == entry.policy.letter),
+            _ => false,
+        }
+    }
+}
+
+// Between min and max of the password's characters come from the set.
+pub struct LetterSet {
+    pub letters: Vec<String>,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl PasswordPolicy for LetterSet {
+    fn name(&self) -> String {
+        format!("letters={}:{}-{}", self.letters.concat(), self.min, self.max)
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+
+        let matching_chars: usize = self.letters.iter()
+            .map(|letter| count_letter(&entry.password, letter))
+            .sum();
+
+        if matching_chars < self.min || matching_chars > self.max {
+            return Err(Violation::new(self.name(), format!(
+                "letters from '{}' appear {} {}, needs {}..={}",
+                self.letters.concat(), matching_chars, times(matching_chars), self.min, self.max,
+            )))
+        }
+
+        Ok(())
+    }
+}
+
+pub struct MatchesRegex(pub Regex);
+
+impl PasswordPolicy for MatchesRegex {
+    fn name(&self) -> String {
+        format!("regex={}", self.0)
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+
+        if !self.0.is_match(&entry.password) {
+            return Err(Violation::new(self.name(), format!("doesn't match /{}/", self.0)))
+        }
+
+        Ok(())
+    }
+}
+
+// No character repeated this many times or more in a row.
+pub struct NoRuns(pub usize);
+
+impl PasswordPolicy for NoRuns {
+    fn name(&self) -> String {
+        format!("no-runs={}", self.0)
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+
+        match longest_run(&entry.password) {
+            Some((c, run)) if run >= self.0 => Err(Violation::new(self.name(), format!(
+                "'{}' repeats {} times in a row, needs fewer than {}", c, run, self.0,
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+// The character with the longest run of repeats, and how long it is.
+fn longest_run(password: &str) -> Option<(&str, usize)> {
+
+    let mut longest: Option<(&str, usize)> = None;
+    let mut run = 0;
+    let mut previous = None;
+
+    for c in password.graphemes(true) {
+        run = if previous == Some(c) { run + 1 } else { 1 };
+        if longest.is_none_or(|(_, length)| run > length) {
+            longest = Some((c, run));
+        }
+        previous = Some(c);
+    }
+
+    longest
+}
+
+// At least this many bits of entropy, going by how often each character
+// turns up in the password itself.
+pub struct MinEntropy(pub f64);
+
+impl PasswordPolicy for MinEntropy {
+    fn name(&self) -> String {
+        format!("entropy={}", self.0)
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+
+        let bits = entropy_bits(&entry.password);
+        if bits < self.0 {
+            return Err(Violation::new(self.name(), format!(
+                "has {:.1} bits of entropy, needs {}", bits, self.0,
+            )))
+        }
+
+        Ok(())
+    }
+}
+
+pub fn entropy_bits(password: &str) -> f64 {
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for c in password.graphemes(true) {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+
+    let length = counts.values().sum::<usize>() as f64;
+    let bits_per_char: f64 = counts.values()
+        .map(|&count| {
+            let p = count as f64 / length;
+            -p * p.log2()
+        })
+        .sum();
+
+    bits_per_char * length
+}
+
+// Names of combined policies, bracketed where they'd otherwise be ambiguous.
+fn joined_names(policies: &[Box<dyn PasswordPolicy>], keyword: &str) -> String {
+    policies.iter()
+        .map(|policy| bracketed(policy.name()))
+        .collect::<Vec<_>>()
+        .join(&format!(" {} ", keyword))
+}
+
+fn bracketed(name: String) -> String {
+    if name.contains(' ') { format!("({})", name) } else { name }
+}
+
+// Fails with the first of its policies that fails.
+pub struct All(pub Vec<Box<dyn PasswordPolicy>>);
+
+impl PasswordPolicy for All {
+    fn name(&self) -> String {
+        joined_names(&self.0, "AND")
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+        self.0.iter().try_for_each(|policy| policy.check(entry))
+    }
+}
+
+// Fails only if every one of its policies does, giving all their reasons.
+pub struct Any(pub Vec<Box<dyn PasswordPolicy>>);
+
+impl PasswordPolicy for Any {
+    fn name(&self) -> String {
+        joined_names(&self.0, "OR")
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+
+        let mut reasons = Vec::new();
+        for policy in &self.0 {
+            match policy.check(entry) {
+                Ok(()) => return Ok(()),
+                Err(violation) => reasons.push(violation.to_string()),
+            }
+        }
+
+        Err(Violation::new(self.name(), reasons.join("; ")))
+    }
+}
+
+pub struct Not(pub Box<dyn PasswordPolicy>);
+
+impl PasswordPolicy for Not {
+    fn name(&self) -> String {
+        format!("NOT {}", bracketed(self.0.name()))
+    }
+
+    fn check(&self, entry: &DatabasePassword) -> Result<(), Violation> {
+        match self.0.check(entry) {
+            Ok(()) => Err(Violation::new(self.name(), format!("{} holds", self.0.name()))),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+pub const POLICY_USAGE: &str = "count, position, letters=<CHARS>:<MIN>-<MAX>, regex=<PATTERN>, \
+no-runs=<N> or entropy=<BITS>, combined with AND, OR, NOT and brackets";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Word(String),
+}
+
+// Words are split on whitespace and brackets, except inside single quotes,
+// so a regex can be given as regex='^(ab)+$'.
+fn tokenize(spec: &str) -> Result<Vec<Token>, String> {
+
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+
+    for c in spec.chars() {
+        match c {
+            '\'' => quoted = !quoted,
+            c if quoted => word.push(c),
+            '(' | ')' | ' ' | '\t' => {
+                if !word.is_empty() {
+                    tokens.push(Token::Word(std::mem::take(&mut word)));
+                }
+                match c {
+                    '(' => tokens.push(Token::Open),
+                    ')' => tokens.push(Token::Close),
+                    _ => {}
+                }
+            }
+            c => word.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(format!("unterminated quote in policy '{}'", spec))
+    }
+
+    if !word.is_empty() {
+        tokens.push(Token::Word(word));
+    }
+
+    Ok(tokens)
+}
+
+fn parse_rule(word: &str) -> Result<Box<dyn PasswordPolicy>, String> {
+
+    let (name, argument) = match word.split_once('=') {
+        Some((name, argument)) => (name, Some(argument)),
+        None => (word, None),
+    };
+
+    let argument = || argument.ok_or_else(|| format!("policy rule '{}' needs a value, like {}=...", name, name));
+    let number = |what: &str| {
+        let argument = argument()?;
+        argument.parse::<f64>().map_err(|_| format!("{} needs {}, not '{}'", name, what, argument))
+    };
+
+    match name {
+        "count" => Ok(Box::new(LetterCount)),
+        "position" => Ok(Box::new(LetterPosition)),
+        "letters" => {
+            let argument = argument()?;
+            let (letters, range) = argument.split_once(':')
+                .ok_or_else(|| format!("expected letters=<CHARS>:<MIN>-<MAX>, not '{}'", word))?;
+            let (min, max) = range.split_once('-')
+                .and_then(|(min, max)| Some((min.parse::<usize>().ok()?, max.parse::<usize>().ok()?)))
+                .ok_or_else(|| format!("invalid range '{}' in '{}'", range, word))?;
+            Ok(Box::new(LetterSet { letters: unique_letters(letters), min, max }))
+        }
+        "regex" => {
+            let argument = argument()?;
+            let regex = Regex::new(argument).map_err(|e| format!("invalid regex '{}': {}", argument, e))?;
+            Ok(Box::new(MatchesRegex(regex)))
+        }
+        "no-runs" => match number("a run length of at least 2")? {
+            n if n >= 2.0 && n.fract() == 0.0 => Ok(Box::new(NoRuns(n as usize))),
+            n => Err(format!("no-runs needs a run length of at least 2, not '{}'", n)),
+        },
+        "entropy" => Ok(Box::new(MinEntropy(number("a number of bits")?))),
+        _ => Err(format!("unknown policy rule '{}', expected one of {}", name, POLICY_USAGE)),
+    }
+}
+
+// Each letter only once, so repeating one doesn't count it twice.
+fn unique_letters(letters: &str) -> Vec<String> {
+
+    let mut unique: Vec<String> = Vec::new();
+    for letter in letters.graphemes(true) {
+        if !unique.iter().any(|u| u == letter) {
+            unique.push(letter.to_string());
+        }
+    }
+
+    unique
+}
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+}
+
+// A recursive descent parser, where NOT binds tightest and OR loosest:
+//
+//     any := all (OR all)*
+//     all := unary (AND unary)*
+//     unary := NOT unary | '(' any ')' | rule
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_any(&mut self) -> Result<Box<dyn PasswordPolicy>, String> {
+
+        let mut policies = vec![self.parse_all()?];
+        while is_keyword(self.peek(), "OR") {
+            self.position += 1;
+            policies.push(self.parse_all()?);
+        }
+
+        Ok(if policies.len() == 1 { policies.pop().unwrap() } else { Box::new(Any(policies)) })
+    }
+
+    fn parse_all(&mut self) -> Result<Box<dyn PasswordPolicy>, String> {
+
+        let mut policies = vec![self.parse_unary()?];
+        while is_keyword(self.peek(), "AND") {
+            self.position += 1;
+            policies.push(self.parse_unary()?);
+        }
+
+        Ok(if policies.len() == 1 { policies.pop().unwrap() } else { Box::new(All(policies)) })
+    }
+
+    fn parse_unary(&mut self) -> Result<Box<dyn PasswordPolicy>, String> {
+
+        if is_keyword(self.peek(), "NOT") {
+            self.position += 1;
+            return Ok(Box::new(Not(self.parse_unary()?)))
+        }
+
+        match self.next() {
+            Some(Token::Open) => {
+                let policy = self.parse_any()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(policy),
+                    _ => Err("missing ')' in policy".to_string()),
+                }
+            }
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("AND") || word.eq_ignore_ascii_case("OR") => {
+                Err(format!("expected a policy rule before '{}'", word))
+            }
+            Some(Token::Word(word)) => parse_rule(&word),
+            Some(Token::Close) => Err("unexpected ')' in policy".to_string()),
+            None => Err("policy ends too soon".to_string()),
+        }
+    }
+}
+
+// Build a policy from something like "count AND NOT (no-runs=3 OR entropy=20)".
+pub fn parse_policy(spec: &str) -> Result<Box<dyn PasswordPolicy>, String> {
+
+    let mut parser = Parser { tokens: tokenize(spec)?, position: 0 };
+    let policy = parser.parse_any()?;
+
+    match parser.peek() {
+        None => Ok(policy),
+        Some(Token::Close) => Err("unexpected ')' in policy".to_string()),
+        Some(Token::Open) => Err("unexpected '(' in policy".to_string()),
+        Some(Token::Word(word)) => Err(format!("expected AND or OR before '{}'", word)),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{count_letter, entropy_bits, letter_at, letter_count, parse_policy, tokenize, Token, Violation};
+    use crate::days::day02::parse_input;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("NOT(count OR regex='^(a b)$')").unwrap(), vec![
+            Token::Word("NOT".to_string()),
+            Token::Open,
+            Token::Word("count".to_string()),
+            Token::Word("OR".to_string()),
+            Token::Word("regex=^(a b)$".to_string()),
+            Token::Close,
+        ]);
+        assert!(tokenize("regex='abc").is_err());
+    }
+
+    #[test]
+    fn test_entropy_bits() {
+        assert_eq!(entropy_bits("aaaa"), 0.0);
+        assert_eq!(entropy_bits("abab"), 4.0);
+        assert_eq!(entropy_bits(""), 0.0);
+    }
+
+    #[test]
+    fn test_policies() {
+        let db = parse_input("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+        let valid = |spec: &str| -> Vec<bool> {
+            let policy = parse_policy(spec).unwrap();
+            db.iter().map(|entry| policy.is_valid(entry)).collect()
+        };
+
+        assert_eq!(valid("count"), vec![true, false, true]);
+        assert_eq!(valid("position"), vec![true, false, false]);
+        assert_eq!(valid("count AND position"), vec![true, false, false]);
+        assert_eq!(valid("NOT count OR position"), vec![true, true, false]);
+        assert_eq!(valid("NOT (count OR position)"), vec![false, true, false]);
+        assert_eq!(valid("letters=ab:2-5"), vec![true, false, false]);
+        assert_eq!(valid("regex='^c(de|cc)'"), vec![false, true, true]);
+        assert_eq!(valid("no-runs=3"), vec![true, true, false]);
+        assert_eq!(valid("entropy=10 and not no-runs=2"), vec![false, false, false]);
+        assert_eq!(valid("entropy=10"), vec![true, true, false]);
+    }
+
+    #[test]
+    fn test_parse_policy_errors() {
+        for spec in &["", "count AND", "count position", "(count", "count)", "OR count",
+                      "size", "regex", "regex=(", "no-runs=1", "entropy=lots", "letters=ab"] {
+            assert!(parse_policy(spec).is_err(), "{}", spec);
+        }
+    }
+
+    #[test]
+    fn test_violations() {
+        let db = parse_input("1-3 b: cdefg\n2-10 c: ccccccccc\n1-3 a: abcde").unwrap();
+        let check = |spec: &str| -> Vec<Result<(), Violation>> {
+            let policy = parse_policy(spec).unwrap();
+            db.iter().map(|entry| policy.check(entry)).collect()
+        };
+
+        assert_eq!(check("count")[0], Err(Violation::new("count", "letter 'b' appears 0 times, needs 1..=3")));
+        assert_eq!(check("position")[1],
+                   Err(Violation::new("position", "position 10 is out of range for a 9-char password")));
+        assert_eq!(check("count AND no-runs=3")[1],
+                   Err(Violation::new("no-runs=3", "'c' repeats 9 times in a row, needs fewer than 3")));
+        assert_eq!(check("NOT (count OR position)")[2],
+                   Err(Violation::new("NOT (count OR position)", "count OR position holds")));
+        assert_eq!(check("regex=x OR entropy=100")[2].as_ref().unwrap_err().reason,
+                   "regex=x: doesn't match /x/; entropy=100: has 11.6 bits of entropy, needs 100");
+    }
+
+    #[test]
+    fn test_letters_are_graphemes() {
+        let decomposed = "e\u{301}";
+
+        assert_eq!(count_letter("abcabc", "b"), 2);
+        assert_eq!(count_letter("héé", "é"), 2);
+        assert_eq!(count_letter("h\u{e9}e\u{301}", decomposed), 1);
+        assert_eq!(count_letter("e\u{301}e", "e"), 1);
+
+        assert_eq!(letter_at("abc", 2), Some("c"));
+        assert_eq!(letter_at("abc", 3), None);
+        assert_eq!(letter_at("🇬🇧xe\u{301}", 2), Some(decomposed));
+        assert_eq!(letter_count("🇬🇧xe\u{301}"), 3);
+    }
+
+    #[test]
+    fn test_unicode_policies() {
+        let db = parse_input("1-2 🇬🇧: 🇬🇧x\n1-3 é: éée\u{301}\n2-3 e\u{301}: ée\u{301}é").unwrap();
+        let valid = |spec: &str| -> Vec<bool> {
+            let policy = parse_policy(spec).unwrap();
+            db.iter().map(|entry| policy.is_valid(entry)).collect()
+        };
+
+        assert_eq!(valid("count"), vec![true, true, false]);
+        assert_eq!(valid("position"), vec![true, true, true]);
+        assert_eq!(valid("letters=éé🇬🇧:2-2"), vec![false, true, true]);
+    }
+}
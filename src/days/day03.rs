@@ -0,0 +1,445 @@
+use std::fmt;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use crate::cli::parse_range;
+use crate::grid::{Grid, Point, Topology};
+use crate::{ParseError, SolveError, Solution};
+
+// The map repeats to the right as far as it needs to, so it's a cylinder.
+fn parse_input(input: &str) -> Result<TobogganMap, ParseError> {
+
+    let map = Grid::parse(input, |c| match c {
+        '.' => Some(Space::Empty),
+        '#' => Some(Space::Tree),
+        _ => None,
+    })?;
+
+    Ok(map.with_topology(Topology::Cylinder))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Space {
+    Empty,
+    Tree,
+}
+
+type TobogganMap = Grid<Space>;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Slope {
+    pub right: usize,
+    pub down: usize,
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "right {}, down {}", self.right, self.down)
+    }
+}
+
+// Travel all the way from (0,0) to the bottom for a given slope.
+// return the number of trees we hit.
+fn count_trees_on_slope(map: &TobogganMap, direction: Slope) -> usize {
+
+    path(map, direction)
+        .filter(|&point| map.get(point) == Some(&Space::Tree))
+        .count()
+}
+
+// Every slope in the ranges along with the trees it hits, safest first.
+// Slopes that hit the same number of trees are ordered by how steep they
+// are.
+pub fn rank_slopes(map: &TobogganMap, rights: RangeInclusive<usize>, downs: RangeInclusive<usize>) -> Vec<(Slope, usize)> {
+
+    let mut ranking: Vec<(Slope, usize)> = downs
+        .flat_map(|down| rights.clone().map(move |right| Slope { right, down }))
+        .map(|slope| (slope, count_trees_on_slope(map, slope)))
+        .collect();
+
+    ranking.sort_by_key(|&(slope, trees)| (trees, slope.down, slope.right));
+
+    ranking
+}
+
+// Where the toboggan is after each step down a slope, with x carrying on
+// past the edge of the map for the grid to wrap back round.
+fn path(map: &TobogganMap, slope: Slope) -> impl Iterator<Item = Point> {
+    (0..map.height())
+        .step_by(slope.down)
+        .enumerate()
+        .map(move |(step, y)| ((step * slope.right) as i64, y as i64))
+}
+
+// How many copies of the map have to go side by side to fit every path.
+fn copies_needed(map: &TobogganMap, slopes: &[Slope]) -> usize {
+
+    let furthest = slopes.iter()
+        .filter_map(|&slope| path(map, slope).last())
+        .map(|(x, _)| x as usize)
+        .max()
+        .unwrap_or(0);
+
+    furthest / map.width().max(1) + 1
+}
+
+// The map drawn out to the given width, with each square as either empty
+// or tree.
+fn draw<P: Copy>(map: &TobogganMap, full_width: usize, empty: P, tree: P) -> Vec<Vec<P>> {
+    (0..map.height() as i64)
+        .map(|y| (0..full_width as i64)
+            .map(|x| if map.get((x, y)) == Some(&Space::Tree) { tree } else { empty })
+            .collect())
+        .collect()
+}
+
+// The map with a slope's path drawn over it, O where the path is clear and
+// X where it hits a tree.
+pub fn render_ascii(map: &TobogganMap, slope: Slope) -> String {
+
+    let full_width = map.width() * copies_needed(map, &[slope]);
+    let mut picture = draw(map, full_width, '.', '#');
+
+    for (x, y) in path(map, slope) {
+        picture[y as usize][x as usize] = if map.get((x, y)) == Some(&Space::Tree) { 'X' } else { 'O' };
+    }
+
+    picture.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const CLEAR_COLOUR: [u8; 3] = [255, 255, 255];
+const TREE_COLOUR: [u8; 3] = [34, 110, 52];
+const SLOPE_COLOURS: [[u8; 3]; 6] = [
+    [230, 25, 75],
+    [0, 130, 200],
+    [245, 130, 48],
+    [145, 30, 180],
+    [70, 200, 220],
+    [240, 50, 230],
+];
+
+// A binary PPM of the map, one pixel per square, with each slope's path in
+// its own colour. Squares where a path hits a tree are a darker shade.
+pub fn render_ppm(map: &TobogganMap, slopes: &[Slope]) -> Vec<u8> {
+
+    let full_width = map.width() * copies_needed(map, slopes);
+    let mut pixels = draw(map, full_width, CLEAR_COLOUR, TREE_COLOUR);
+
+    for (i, &slope) in slopes.iter().enumerate() {
+        let colour = SLOPE_COLOURS[i % SLOPE_COLOURS.len()];
+        for (x, y) in path(map, slope) {
+            pixels[y as usize][x as usize] = if map.get((x, y)) == Some(&Space::Tree) { colour.map(|c| c / 2) } else { colour };
+        }
+    }
+
+    let mut image = format!("P6\n{} {}\n255\n", full_width, map.height()).into_bytes();
+    image.extend(pixels.iter().flatten().flatten());
+
+    image
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Render {
+    #[default]
+    Off,
+    Ascii,
+    Ppm(PathBuf),
+}
+
+fn trees(n: usize) -> String {
+    format!("{} {}", n, if n == 1 { "tree" } else { "trees" })
+}
+
+// Slopes tied for the given number of trees, written out in a list.
+fn slopes_hitting(ranking: &[(Slope, usize)], trees: usize) -> String {
+    ranking.iter()
+        .filter(|&&(_, t)| t == trees)
+        .map(|(slope, _)| slope.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// The safest and most dangerous slopes, followed by every slope in order.
+fn format_ranking(ranking: &[(Slope, usize)]) -> String {
+
+    let fewest = ranking.first().map_or(0, |&(_, trees)| trees);
+    let most = ranking.last().map_or(0, |&(_, trees)| trees);
+
+    let mut lines = vec![
+        format!("safest: {} ({})", slopes_hitting(ranking, fewest), trees(fewest)),
+        format!("most dangerous: {} ({})", slopes_hitting(ranking, most), trees(most)),
+    ];
+    for (rank, &(slope, hit)) in ranking.iter().enumerate() {
+        lines.push(format!("{:>4}. {}: {}", rank + 1, slope, trees(hit)));
+    }
+
+    lines.join("\n")
+}
+
+// Given --right or --down, part one counts the trees on the safest slope
+// in those ranges instead of on just the one, and the report ranks every
+// slope. Whichever isn't given stays as it is in the puzzle.
+//
+// --render ascii draws part one's path (or the safest one found) in the
+// report, and --render <FILE>.ppm saves an image of part two's paths.
+#[derive(Default)]
+pub struct DayThree {
+    pub rights: Option<RangeInclusive<usize>>,
+    pub downs: Option<RangeInclusive<usize>>,
+    pub render: Render,
+}
+
+impl DayThree {
+    // Every slope in the ranges given, or None to stick to part one's.
+    fn ranking(&self, map: &TobogganMap) -> Option<Vec<(Slope, usize)>> {
+
+        if self.rights.is_none() && self.downs.is_none() {
+            return None
+        }
+
+        let rights = self.rights.clone().unwrap_or(PART_ONE_SLOPE.right..=PART_ONE_SLOPE.right);
+        let downs = self.downs.clone().unwrap_or(PART_ONE_SLOPE.down..=PART_ONE_SLOPE.down);
+
+        Some(rank_slopes(map, rights, downs))
+    }
+}
+
+const PART_ONE_SLOPE: Slope = Slope { right: 3, down: 1 };
+
+const PART_TWO_SLOPES: [Slope; 5] = [
+    Slope { right: 1, down: 1 },
+    Slope { right: 3, down: 1 },
+    Slope { right: 5, down: 1 },
+    Slope { right: 7, down: 1 },
+    Slope { right: 1, down: 2 },
+];
+
+impl Solution for DayThree {
+    const DAY: u32 = 3;
+
+    type Input = TobogganMap;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        match self.ranking(input) {
+            None => Ok(part_one(input)),
+            Some(ranking) => ranking.first()
+                .map(|&(_, trees)| trees)
+                .ok_or_else(|| SolveError::new("there are no slopes in the --right and --down ranges")),
+        }
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part_two(input))
+    }
+
+    fn report(&self, input: &Self::Input) -> Result<Option<String>, SolveError> {
+
+        let ranking = self.ranking(input).filter(|ranking| !ranking.is_empty());
+        let mut sections = Vec::new();
+
+        if let Some(ranking) = &ranking {
+            sections.push(format_ranking(ranking));
+        }
+
+        match &self.render {
+            Render::Off => {}
+            Render::Ascii => {
+                let slope = ranking.as_ref().map_or(PART_ONE_SLOPE, |ranking| ranking[0].0);
+                sections.push(render_ascii(input, slope));
+            }
+            Render::Ppm(path) => {
+                fs::write(path, render_ppm(input, &PART_TWO_SLOPES))
+                    .map_err(|e| SolveError::new(format!("could not write {}: {}", path.display(), e)))?;
+                sections.push(format!("saved part two's paths to {}", path.display()));
+            }
+        }
+
+        if sections.is_empty() {
+            return Ok(None)
+        }
+
+        Ok(Some(sections.join("\n\n")))
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+
+        let range = || parse_range(value).map_err(|e| format!("--{}: {}", name, e));
+
+        match name {
+            "render" => {
+                self.render = match value {
+                    "ascii" => Render::Ascii,
+                    path if path.ends_with(".ppm") => Render::Ppm(PathBuf::from(path)),
+                    _ => return Err(format!("--render takes 'ascii' or a .ppm file to write, not '{}'", value)),
+                };
+            }
+            "right" => self.rights = Some(range()?),
+            "down" => {
+                let range = range()?;
+                if *range.start() == 0 {
+                    return Err("--down can't include 0, or we'd never reach the bottom".to_string())
+                }
+                self.downs = Some(range);
+            }
+            _ => return Err(format!("day 3 has no option --{}", name)),
+        }
+        Ok(())
+    }
+}
+
+fn part_one(map: &TobogganMap) -> usize {
+    count_trees_on_slope(map, PART_ONE_SLOPE)
+}
+
+fn part_two(map: &TobogganMap) -> usize {
+
+    PART_TWO_SLOPES.iter().fold(1, |product, &slope| product * count_trees_on_slope(map, slope))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::parse_input;
+    use super::part_one;
+    use super::part_two;
+    use super::{rank_slopes, render_ascii, render_ppm, DayThree, Render, Slope, PART_TWO_SLOPES};
+    use crate::Solution;
+
+    const EXAMPLE_INPUT: &str =
+"..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn test_day_three_part_one() {
+        let example_input =
+"..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+        let map = parse_input(example_input).unwrap();
+        assert_eq!(part_one(&map), 7);
+    }
+
+    #[test]
+    fn test_day_three_part_two() {
+        let example_input =
+"..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+        let map = parse_input(example_input).unwrap();
+        assert_eq!(part_two(&map), 336);
+    }
+
+    #[test]
+    fn test_rank_slopes() {
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
+        let ranking = rank_slopes(&map, 1..=7, 1..=2);
+
+        assert_eq!(ranking.len(), 14);
+        assert_eq!(ranking.last(), Some(&(Slope { right: 3, down: 1 }, 7)));
+        assert!(ranking.contains(&(Slope { right: 1, down: 2 }, 2)));
+        assert!(ranking.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn test_slope_options() {
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
+
+        let mut day = DayThree::default();
+        day.set_option("right", "1..=3").unwrap();
+        assert!(day.set_option("down", "0..=2").is_err());
+        assert!(day.set_option("left", "1").is_err());
+        assert_eq!(day.set_option("left", "x"), Err(String::from("day 3 has no option --left")));
+
+        assert_eq!(day.part_one(&map), Ok(1));
+        assert_eq!(day.part_two(&map), Ok(336));
+        assert_eq!(day.report(&map).unwrap().unwrap(), "safest: right 2, down 1 (1 tree)
+most dangerous: right 3, down 1 (7 trees)
+   1. right 2, down 1: 1 tree
+   2. right 1, down 1: 2 trees
+   3. right 3, down 1: 7 trees");
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
+        let picture = render_ascii(&map, Slope { right: 3, down: 1 });
+        let rows: Vec<&str> = picture.lines().collect();
+
+        assert_eq!(rows.len(), 11);
+        assert_eq!(rows[0], "O.##.........##.........##.......");
+        assert_eq!(rows[2], ".#....X..#..#....#..#..#....#..#.");
+        assert_eq!(rows[10], ".#..#...#.#.#..#...#.#.#..#...X.#");
+
+        let picture = render_ascii(&map, Slope { right: 1, down: 2 });
+        assert_eq!(picture.lines().nth(2), Some(".X....#..#."));
+    }
+
+    #[test]
+    fn test_render_ppm() {
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
+        let image = render_ppm(&map, &[Slope { right: 1, down: 1 }, Slope { right: 7, down: 1 }]);
+
+        let header = "P6\n77 11\n255\n";
+        assert!(image.starts_with(header.as_bytes()));
+        assert_eq!(image.len(), header.len() + 77 * 11 * 3);
+    }
+
+    #[test]
+    fn test_render_report() {
+        let map = parse_input(EXAMPLE_INPUT).unwrap();
+
+        let day = DayThree { render: Render::Ascii, ..DayThree::default() };
+        assert_eq!(day.part_one(&map), Ok(7));
+        assert_eq!(day.report(&map).unwrap(), Some(render_ascii(&map, Slope { right: 3, down: 1 })));
+
+        let day = DayThree { rights: Some(1..=3), render: Render::Ascii, ..DayThree::default() };
+        let report = day.report(&map).unwrap().unwrap();
+        assert!(report.ends_with(&format!("\n\n{}", render_ascii(&map, Slope { right: 2, down: 1 }))));
+
+        // The image is only written by the report, not while solving.
+        let path = std::env::temp_dir().join(format!("aoc-day03-{}.ppm", std::process::id()));
+        let day = DayThree { render: Render::Ppm(path.clone()), ..DayThree::default() };
+        assert_eq!(day.part_two(&map), Ok(336));
+        assert!(!path.exists());
+
+        assert!(day.report(&map).unwrap().is_some());
+        assert_eq!(std::fs::read(&path).unwrap(), render_ppm(&map, &PART_TWO_SLOPES));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
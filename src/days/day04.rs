@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::LineError;
+use crate::records::{Record, Records};
+use crate::{DayError, ParseError, SolveError, Solution};
+
+pub mod height;
+pub mod schema;
+
+use schema::{Schema, Violation};
+
+pub(crate) fn parse_input(input: &str) -> Result<Vec<Passport>, ParseError> {
+
+    read_passports(input.as_bytes()).collect()
+}
+
+// Passports one at a time from anything that can be read line by line, for
+// batches too big to read into memory first. This is what --stream uses.
+pub fn read_passports<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Passport, ParseError>> {
+
+    Records::new(reader).map(|record| parse_passport(&record?))
+}
+
+fn parse_passport(record: &Record) -> Result<Passport, ParseError> {
+
+    let mut passport = Passport { lines: record.line_range(), fields: Vec::new() };
+
+    for (number, line) in record.numbered_lines() {
+        for field in line.split_ascii_whitespace() {
+            let field = parse_passport_field(field, number)
+                .map_err(|reason| ParseError::new(number, line, LineError::at(line, field, reason)))?;
+            passport.fields.push(field);
+        }
+    }
+
+    Ok(passport)
+}
+
+// Only the shape of a field is checked here. Which fields are allowed, and
+// what they may hold, is up to the schema.
+fn parse_passport_field(field: &str, line: usize) -> Result<Field, String> {
+
+    let (key, value) = field.split_once(':')
+        .ok_or_else(|| format!("expected a field like 'key:value' but found '{}'", field))?;
+
+    if key.is_empty() {
+        return Err(format!("field '{}' has no key", field))
+    }
+
+    Ok(Field { key: key.to_string(), value: value.to_string(), line })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Passport {
+    pub lines: RangeInclusive<usize>,
+    pub fields: Vec<Field>,
+}
+
+impl Passport {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.iter().find(|field| field.key == key).map(|field| field.value.as_str())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ReportMode {
+    Off,
+    Summary,
+    Invalid,
+}
+
+impl FromStr for ReportMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(ReportMode::Off),
+            "summary" => Ok(ReportMode::Summary),
+            "invalid" => Ok(ReportMode::Invalid),
+            unknown => Err(format!("unknown report '{}', expected off, summary or invalid", unknown)),
+        }
+    }
+}
+
+// Why passports failed: how many failed for each reason, most common first,
+// and, if asked for, every failing passport by the line it starts on.
+#[derive(Debug, PartialEq)]
+pub struct FailureSummary {
+    pub valid: usize,
+    pub total: usize,
+    pub reasons: Vec<(String, usize)>,
+    pub invalid: Vec<(usize, Vec<Violation>)>,
+}
+
+impl FailureSummary {
+    fn new(mode: ReportMode, passports: &[Passport], validate: impl Fn(&Passport) -> Vec<Violation>) -> Self {
+
+        let mut reasons: HashMap<String, usize> = HashMap::new();
+        let mut invalid = Vec::new();
+        let mut valid = 0;
+
+        for passport in passports {
+            let violations = validate(passport);
+            if violations.is_empty() {
+                valid += 1;
+                continue
+            }
+
+            for violation in &violations {
+                *reasons.entry(violation.reason()).or_insert(0) += 1;
+            }
+
+            if mode == ReportMode::Invalid {
+                invalid.push((*passport.lines.start(), violations));
+            }
+        }
+
+        let mut reasons: Vec<(String, usize)> = reasons.into_iter().collect();
+        reasons.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+
+        FailureSummary { valid, total: passports.len(), reasons, invalid }
+    }
+}
+
+impl fmt::Display for FailureSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+
+        write!(f, "{} of {} valid", self.valid, self.total)?;
+
+        if !self.reasons.is_empty() {
+            write!(f, "\nmost common failures:")?;
+            let width = self.reasons[0].1.to_string().len();
+            for (reason, count) in &self.reasons {
+                write!(f, "\n  {:>width$}  {}", count, reason, width = width)?;
+            }
+        }
+
+        for (line, violations) in &self.invalid {
+            let violations: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+            write!(f, "\nline {}: {}", line, violations.join("; "))?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct DayFour {
+    pub schema: Schema,
+    pub report: ReportMode,
+}
+
+impl Default for DayFour {
+    fn default() -> Self {
+        DayFour { schema: Schema::puzzle(), report: ReportMode::Off }
+    }
+}
+
+impl Solution for DayFour {
+    const DAY: u32 = 4;
+
+    type Input = Vec<Passport>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part_one(&self.schema, input))
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part_two(&self.schema, input))
+    }
+
+    fn solve_reader(&self, reader: &mut dyn BufRead) -> Result<(Self::Output, Self::Output), DayError> {
+        Ok(count_passports(&self.schema, reader)?)
+    }
+
+    // Fields the schema doesn't know about are reported rather than
+    // rejected, whether or not a report on the failures was asked for.
+    fn report(&self, input: &Self::Input) -> Result<Option<String>, SolveError> {
+
+        let mut sections = Vec::new();
+
+        if self.report != ReportMode::Off {
+            let part_one = FailureSummary::new(self.report, input, |p| self.schema.missing_fields(p));
+            let part_two = FailureSummary::new(self.report, input, |p| self.schema.validate(p));
+            sections.push(format!("part one: {}", part_one));
+            sections.push(format!("part two: {}", part_two));
+        }
+
+        let unknown = unknown_fields(&self.schema, input);
+        if !unknown.is_empty() {
+            let unknown: Vec<String> = unknown.iter()
+                .map(|(key, lines)| match lines.len() {
+                    1 => format!("{} (line {})", key, lines[0]),
+                    n => format!("{} ({} times, first on line {})", key, n, lines[0]),
+                })
+                .collect();
+            sections.push(format!("unknown fields: {}", unknown.join(", ")));
+        }
+
+        if sections.is_empty() {
+            return Ok(None)
+        }
+
+        Ok(Some(sections.join("\n\n")))
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+
+        match name {
+            "schema" => self.schema = Schema::load(Path::new(value))?,
+            "report" => self.report = value.parse::<ReportMode>()?,
+            _ => return Err(format!("day 4 has no option --{}", name)),
+        }
+
+        Ok(())
+    }
+}
+
+// Both parts' counts a passport at a time, without keeping any of them.
+pub fn count_passports<R: BufRead>(schema: &Schema, reader: R) -> Result<(usize, usize), ParseError> {
+
+    let mut counts = (0, 0);
+
+    for passport in read_passports(reader) {
+        let passport = passport?;
+        if schema.has_required_fields(&passport) {
+            counts.0 += 1;
+        }
+        if schema.is_valid(&passport) {
+            counts.1 += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+fn part_one(schema: &Schema, passports: &[Passport]) -> usize {
+
+    passports.iter().filter(|p| schema.has_required_fields(p)).count()
+}
+
+fn part_two(schema: &Schema, passports: &[Passport]) -> usize {
+
+    passports.iter().filter(|p| schema.is_valid(p)).count()
+}
+
+// Grouped by key, in the order they first turn up.
+fn unknown_fields(schema: &Schema, passports: &[Passport]) -> Vec<(String, Vec<usize>)> {
+
+    let mut unknown: Vec<(String, Vec<usize>)> = Vec::new();
+
+    for field in passports.iter().flat_map(|p| schema.unknown_fields(p)) {
+        match unknown.iter_mut().find(|(key, _)| *key == field.key) {
+            Some((_, lines)) => lines.push(field.line),
+            None => unknown.push((field.key.clone(), vec![field.line])),
+        }
+    }
+
+    unknown
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::schema::Schema;
+    use std::io::BufReader;
+
+    use super::{count_passports, parse_input, part_one, part_two, read_passports, unknown_fields, DayFour, ReportMode};
+    use crate::Solution;
+
+    #[test]
+    fn test_parse_error_location() {
+        let error = parse_input("byr:1937 iyr:2017\n\nhgt:183cm\neyr:2020 xyz").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 9));
+
+        let error = parse_input(":1937 iyr:2017").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 0));
+
+        let error = parse_input("byr:1937\r\n \r\nhgt:183cm\r\neyr:2020 xyz\r\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (4, 9, "eyr:2020 xyz"));
+
+        // Unknown keys and bad values are for the schema to deal with.
+        assert!(parse_input("byr:19x7 xyz:1").is_ok());
+    }
+
+    #[test]
+    fn test_record_separators() {
+        let passports = parse_input("\r\nbyr:1937 iyr:2017\r\nhgt:183cm\r\n\t\r\n  \r\neyr:2020\n \ncid:1\n").unwrap();
+        let lines: Vec<_> = passports.iter().map(|p| p.lines.clone()).collect();
+
+        assert_eq!(lines, vec![2..=3, 6..=6, 8..=8]);
+        assert_eq!(passports[0].get("hgt"), Some("183cm"));
+    }
+
+    #[test]
+    fn test_read_passports_from_reader() {
+        let input = "byr:1937 iyr:2017\r\nhgt:183cm\r\n\r\neyr:2020\n\nbyr:19 x";
+        // A tiny buffer, so records have to be put together across reads.
+        let passports: Vec<_> = read_passports(BufReader::with_capacity(4, input.as_bytes())).collect();
+
+        assert_eq!(passports.len(), 3);
+        assert_eq!(passports[0].as_ref().unwrap().lines, 1..=2);
+        assert_eq!(passports[0].as_ref().unwrap().get("hgt"), Some("183cm"));
+        assert_eq!(passports[1].as_ref().unwrap().get("eyr"), Some("2020"));
+        assert_eq!(passports[2].as_ref().unwrap_err().line, 6);
+    }
+
+    #[test]
+    fn test_count_passports_matches_parts() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in byr:1990";
+
+        let schema = Schema::puzzle();
+        let passports = parse_input(input).unwrap();
+        let counts = count_passports(&schema, BufReader::new(input.as_bytes())).unwrap();
+
+        assert_eq!(counts, (part_one(&schema, &passports), part_two(&schema, &passports)));
+        assert_eq!(counts, (2, 2));
+
+        let day = DayFour::default();
+        assert_eq!(day.solve_reader(&mut BufReader::new(input.as_bytes())).unwrap(), counts);
+        assert!(day.solve_reader(&mut "byr:1937 x".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_reported() {
+        let passports = parse_input("byr:1937 iyr:2017\n\nhgt:183cm\neyr:2020 xyz:1\n\nabc:2 xyz:3").unwrap();
+
+        assert_eq!(unknown_fields(&Schema::puzzle(), &passports), vec![(String::from("xyz"), vec![4, 6]), (String::from("abc"), vec![6])]);
+        assert_eq!(DayFour::default().report(&passports).unwrap().unwrap(), "unknown fields: xyz (2 times, first on line 4), abc (line 6)");
+        assert_eq!(DayFour::default().report(&passports[..1].to_vec()), Ok(None));
+    }
+
+    #[test]
+    fn test_day_four_part_one() {
+        let example_input =
+String::from("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in");
+
+        assert_eq!(part_one(&Schema::puzzle(), &parse_input(&example_input).unwrap()), 2);
+    }
+
+    #[test]
+    fn test_day_four_part_two() {
+        let example_invalid =
+String::from("eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007");
+
+        let example_valid =
+String::from("pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719");
+
+        assert_eq!(part_two(&Schema::puzzle(), &parse_input(&example_invalid).unwrap()), 0);
+        assert_eq!(part_two(&Schema::puzzle(), &parse_input(&example_valid).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_failure_report() {
+        let passports = parse_input("byr:2007 iyr:2010 eyr:2030 hgt:190 hcl:#123abc ecl:brn pid:012345678
+
+iyr:2010 eyr:2030 hgt:190cm hcl:#123abc ecl:brn pid:012345678
+
+byr:1980 iyr:2010 eyr:2030 hgt:190in hcl:#123abc ecl:wat pid:012345678
+
+byr:1980 iyr:2010 eyr:2030 hgt:190cm hcl:#123abc ecl:brn pid:012345678").unwrap();
+
+        let day = DayFour { report: ReportMode::Summary, ..DayFour::default() };
+        assert_eq!(day.part_two(&passports), Ok(1));
+        assert_eq!(day.report(&passports).unwrap().unwrap(), "part one: 3 of 4 valid
+most common failures:
+  1  missing byr
+
+part two: 1 of 4 valid
+most common failures:
+  1  byr out of range
+  1  ecl badly formatted
+  1  hgt has no unit
+  1  hgt out of range
+  1  missing byr");
+
+        let day = DayFour { report: ReportMode::Invalid, ..DayFour::default() };
+        assert!(day.report(&passports[2..].to_vec()).unwrap().unwrap().ends_with("part two: 1 of 2 valid
+most common failures:
+  1  ecl badly formatted
+  1  hgt out of range
+line 5: hgt 4826mm is outside 1499..=1930mm; ecl 'wat' should be one of amb, blu, brn, gry, grn, hzl, oth"));
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+
+// Heights are kept in micrometres, which every unit here is a whole number
+// of, so converting between them never loses anything until it's displayed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LengthUnit {
+    Millimeter,
+    Centimeter,
+    Inch,
+    // Written like 5'11", or 6' for a whole number of feet.
+    FeetInches,
+}
+
+impl LengthUnit {
+    fn micrometres(self) -> u64 {
+        match self {
+            LengthUnit::Millimeter => 1_000,
+            LengthUnit::Centimeter => 10_000,
+            LengthUnit::Inch | LengthUnit::FeetInches => 25_400,
+        }
+    }
+}
+
+impl FromStr for LengthUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mm" => Ok(LengthUnit::Millimeter),
+            "cm" => Ok(LengthUnit::Centimeter),
+            "in" => Ok(LengthUnit::Inch),
+            "ft'in\"" => Ok(LengthUnit::FeetInches),
+            unknown => Err(format!("unknown unit '{}', expected mm, cm, in or ft'in\"", unknown)),
+        }
+    }
+}
+
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LengthUnit::Millimeter => write!(f, "mm"),
+            LengthUnit::Centimeter => write!(f, "cm"),
+            LengthUnit::Inch => write!(f, "in"),
+            LengthUnit::FeetInches => write!(f, "ft'in\""),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Height {
+    micrometres: u64,
+    unit: LengthUnit,
+}
+
+impl Height {
+    pub fn new(value: u32, unit: LengthUnit) -> Self {
+        Height { micrometres: value as u64 * unit.micrometres(), unit }
+    }
+
+    pub fn unit(&self) -> LengthUnit {
+        self.unit
+    }
+
+    // The same height, to be shown in another unit.
+    pub fn to(self, unit: LengthUnit) -> Self {
+        Height { unit, ..self }
+    }
+
+    // How many of the unit this height is, which for feet and inches is
+    // the total number of inches.
+    pub fn value_in(&self, unit: LengthUnit) -> f64 {
+        self.micrometres as f64 / unit.micrometres() as f64
+    }
+
+    // The height rounded to the nearest millimetre, which is the unit that
+    // ranges of valid heights are given in.
+    pub fn millimetres(&self) -> u64 {
+        (self.micrometres + 500) / 1_000
+    }
+}
+
+impl FromStr for Height {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        lazy_static! {
+            static ref HEIGHT_REGEX: Regex = Regex::new(
+                r#"^(?:(?P<value>[0-9]+)(?P<unit>mm|cm|in)|(?P<feet>[0-9]+)'(?:(?P<inches>[0-9]+)")?)$"#
+            ).unwrap();
+        }
+
+        let matches = HEIGHT_REGEX.captures(s)
+            .ok_or_else(|| format!("'{}' isn't a height like 183cm, 1830mm, 72in or 6'0\"", s))?;
+
+        let number = |name: &str| matches.name(name)
+            .map_or(Ok(0), |m| m.as_str().parse::<u32>())
+            .map_err(|e| format!("failed to parse height '{}': {}", s, e));
+
+        if let Some(unit) = matches.name("unit") {
+            return Ok(Height::new(number("value")?, unit.as_str().parse()?))
+        }
+
+        let (feet, inches) = (number("feet")?, number("inches")?);
+        if inches >= 12 {
+            return Err(format!("height '{}' has {} inches, which should be written as feet", s, inches))
+        }
+
+        let inches = feet.checked_mul(12).and_then(|n| n.checked_add(inches))
+            .ok_or_else(|| format!("height '{}' is too big", s))?;
+
+        Ok(Height::new(inches, LengthUnit::FeetInches))
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+
+        let value = self.value_in(self.unit);
+
+        match self.unit {
+            LengthUnit::FeetInches => {
+                let inches = value.round() as u64;
+                write!(f, "{}'{}\"", inches / 12, inches % 12)
+            }
+            unit if value.fract() == 0.0 => write!(f, "{}{}", value, unit),
+            unit => write!(f, "{:.1}{}", value, unit),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Height, LengthUnit};
+
+    #[test]
+    fn test_parse_height() {
+        assert_eq!("183cm".parse(), Ok(Height::new(183, LengthUnit::Centimeter)));
+        assert_eq!("1830mm".parse::<Height>().unwrap().millimetres(), 1830);
+        assert_eq!("72in".parse::<Height>().unwrap().unit(), LengthUnit::Inch);
+        assert_eq!("6'".parse(), Ok(Height::new(72, LengthUnit::FeetInches)));
+        assert_eq!("5'11\"".parse(), Ok(Height::new(71, LengthUnit::FeetInches)));
+
+        for bad in &["", "abc", "170", "170cmx", "x170cm", "170 cm", "170ft", "5'12\"", "5'11", "99999999999cm"] {
+            assert!(bad.parse::<Height>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_convert_height() {
+        let height: Height = "6'1\"".parse().unwrap();
+
+        assert_eq!(height.value_in(LengthUnit::Inch), 73.0);
+        assert_eq!(height.to(LengthUnit::Centimeter).to_string(), "185.4cm");
+        assert_eq!(height.to(LengthUnit::Millimeter).to_string(), "1854.2mm");
+        assert_eq!(height.millimetres(), 1854);
+        assert_eq!(Height::new(74, LengthUnit::Inch).to(LengthUnit::FeetInches).to_string(), "6'2\"");
+        assert_eq!(Height::new(180, LengthUnit::Centimeter).to(LengthUnit::FeetInches).to_string(), "5'11\"");
+        assert_eq!(Height::new(59, LengthUnit::Inch).millimetres(), 1499);
+        assert_eq!("429496880cm".parse::<Height>().unwrap().millimetres(), 4_294_968_800);
+    }
+}
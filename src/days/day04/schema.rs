@@ -0,0 +1,400 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::cli::parse_range;
+
+use super::height::Height;
+use super::Passport;
+
+// The rules from the puzzle, which are used unless another schema is given.
+const PUZZLE_SCHEMA: &str = include_str!("passport.toml");
+
+// Something a field's value has to satisfy.
+#[derive(Debug)]
+pub enum Rule {
+    Year(RangeInclusive<u64>),
+    Regex(Regex),
+    OneOf(Vec<String>),
+    Units(Vec<(String, RangeInclusive<u64>)>),
+    // In millimetres, whichever unit the height was given in.
+    Height(RangeInclusive<u64>),
+}
+
+// Why a passport failed the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    MissingField(String),
+    OutOfRange { field: String, value: u64, range: RangeInclusive<u64>, unit: String },
+    BadFormat { field: String, value: String, expected: String },
+    UnitMissing { field: String, value: String, units: Vec<String> },
+}
+
+impl Violation {
+    // What went wrong without the value it went wrong with, so that the same
+    // failure on different passports can be counted together.
+    pub fn reason(&self) -> String {
+        match self {
+            Violation::MissingField(field) => format!("missing {}", field),
+            Violation::OutOfRange { field, .. } => format!("{} out of range", field),
+            Violation::BadFormat { field, .. } => format!("{} badly formatted", field),
+            Violation::UnitMissing { field, .. } => format!("{} has no unit", field),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::MissingField(field) => write!(f, "missing {}", field),
+            Violation::OutOfRange { field, value, range, unit } => {
+                write!(f, "{} {}{} is outside {}..={}{}", field, value, unit, range.start(), range.end(), unit)
+            }
+            Violation::BadFormat { field, value, expected } => write!(f, "{} '{}' should be {}", field, value, expected),
+            Violation::UnitMissing { field, value, units } => {
+                write!(f, "{} '{}' has no unit, expected one of {}", field, value, units.join(", "))
+            }
+        }
+    }
+}
+
+impl Rule {
+    pub fn check(&self, field: &str, value: &str) -> Result<(), Violation> {
+
+        let bad_format = |expected: String| Violation::BadFormat {
+            field: field.to_string(),
+            value: value.to_string(),
+            expected,
+        };
+
+        let out_of_range = |number: u64, range: &RangeInclusive<u64>, unit: &str| Violation::OutOfRange {
+            field: field.to_string(),
+            value: number,
+            range: range.clone(),
+            unit: unit.to_string(),
+        };
+
+        match self {
+            Rule::Year(range) => {
+                if value.len() != 4 || !value.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(bad_format(String::from("a four digit year")))
+                }
+                let year = value.parse::<u64>().unwrap();
+                if !range.contains(&year) {
+                    return Err(out_of_range(year, range, ""))
+                }
+            }
+            Rule::Regex(regex) => {
+                if !regex.is_match(value) {
+                    return Err(bad_format(format!("like {}", regex)))
+                }
+            }
+            Rule::OneOf(allowed) => {
+                if !allowed.iter().any(|a| a == value) {
+                    return Err(bad_format(format!("one of {}", allowed.join(", "))))
+                }
+            }
+            Rule::Units(units) => {
+                let names: Vec<String> = units.iter().map(|(name, _)| name.clone()).collect();
+
+                let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+                let (number, unit) = value.split_at(split);
+                let number = number.parse::<u64>()
+                    .map_err(|_| bad_format(format!("a number followed by {}", names.join(" or "))))?;
+
+                if unit.is_empty() {
+                    return Err(Violation::UnitMissing { field: field.to_string(), value: value.to_string(), units: names })
+                }
+
+                let range = units.iter().find(|(name, _)| name == unit).map(|(_, range)| range)
+                    .ok_or_else(|| bad_format(format!("in {}", names.join(" or "))))?;
+
+                if !range.contains(&number) {
+                    return Err(out_of_range(number, range, unit))
+                }
+            }
+            Rule::Height(range) => {
+                if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+                    let units = ["mm", "cm", "in", "ft'in\""].iter().map(|unit| unit.to_string()).collect();
+                    return Err(Violation::UnitMissing { field: field.to_string(), value: value.to_string(), units })
+                }
+
+                let height = value.parse::<Height>()
+                    .map_err(|_| bad_format(String::from("a height like 183cm, 1830mm, 72in or 6'0\"")))?;
+
+                if !range.contains(&height.millimetres()) {
+                    return Err(out_of_range(height.millimetres(), range, "mm"))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct FieldSchema {
+    pub name: String,
+    pub required: bool,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+pub struct Schema {
+    pub fields: Vec<FieldSchema>,
+}
+
+// What sort of value this is, for error messages.
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "a table",
+    }
+}
+
+fn parse_bounds(field: &str, rule: &str, value: &Value) -> Result<RangeInclusive<u64>, String> {
+
+    let text = value.as_str()
+        .ok_or_else(|| format!("{}.{} should be a range like \"1..=10\", not {}", field, rule, kind(value)))?;
+
+    let range = parse_range(text).map_err(|e| format!("{}.{}: {}", field, rule, e))?;
+    let bound = |n: usize| u64::try_from(n).map_err(|_| format!("{}.{}: {} is too big", field, rule, n));
+
+    Ok(bound(*range.start())?..=bound(*range.end())?)
+}
+
+fn parse_rule(field: &str, name: &str, value: &Value) -> Result<Rule, String> {
+
+    let string = |value: &Value| value.as_str().map(String::from)
+        .ok_or_else(|| format!("{}.{} should be a string, not {}", field, name, kind(value)));
+
+    match name {
+        "year" => Ok(Rule::Year(parse_bounds(field, name, value)?)),
+        "height" => Ok(Rule::Height(parse_bounds(field, name, value)?)),
+        "regex" => {
+            let pattern = string(value)?;
+            let regex = Regex::new(&pattern).map_err(|e| format!("{}.regex: invalid regex '{}': {}", field, pattern, e))?;
+            Ok(Rule::Regex(regex))
+        }
+        "one_of" => {
+            let values = value.as_array()
+                .ok_or_else(|| format!("{}.one_of should be an array of strings, not {}", field, kind(value)))?;
+            Ok(Rule::OneOf(values.iter().map(string).collect::<Result<_, _>>()?))
+        }
+        "unit" => {
+            let units = value.as_object()
+                .ok_or_else(|| format!("{}.unit should be a table of units and their ranges, not {}", field, kind(value)))?;
+            let units = units.iter()
+                .map(|(unit, range)| Ok((unit.clone(), parse_bounds(field, &format!("unit.{}", unit), range)?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(Rule::Units(units))
+        }
+        unknown => Err(format!("unknown rule '{}' for field '{}', expected year, height, regex, one_of or unit", unknown, field)),
+    }
+}
+
+impl Schema {
+    pub fn puzzle() -> Self {
+        Schema::parse(PUZZLE_SCHEMA, false).expect("the puzzle's passport schema is broken")
+    }
+
+    // A schema is a table of fields, each of which is a table of rules.
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+
+        let fields = value.as_object().ok_or("a passport schema should be a table of fields")?;
+
+        let fields = fields.iter()
+            .map(|(name, rules)| {
+                let rules = rules.as_object()
+                    .ok_or_else(|| format!("field '{}' should be a table of rules, not {}", name, kind(rules)))?;
+
+                let mut field = FieldSchema { name: name.clone(), required: true, rules: Vec::new() };
+                for (rule, value) in rules {
+                    if rule == "required" {
+                        field.required = value.as_bool()
+                            .ok_or_else(|| format!("{}.required should be true or false, not {}", name, kind(value)))?;
+                    }
+                    else {
+                        field.rules.push(parse_rule(name, rule, value)?);
+                    }
+                }
+                Ok(field)
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Schema { fields })
+    }
+
+    pub fn parse(text: &str, json: bool) -> Result<Self, String> {
+
+        let value: Value = if json {
+            serde_json::from_str(text).map_err(|e| e.to_string())?
+        }
+        else {
+            toml::from_str(text).map_err(|e| e.to_string())?
+        };
+
+        Schema::from_value(&value)
+    }
+
+    // Files ending in .json are read as JSON, and anything else as TOML.
+    pub fn load(path: &Path) -> Result<Self, String> {
+
+        let text = fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+        let json = path.extension().is_some_and(|extension| extension == "json");
+
+        Schema::parse(&text, json).map_err(|e| format!("bad passport schema in {}\n{}", path.display(), e))
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldSchema> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    pub fn missing_fields(&self, passport: &Passport) -> Vec<Violation> {
+        self.fields.iter()
+            .filter(|field| field.required && passport.get(&field.name).is_none())
+            .map(|field| Violation::MissingField(field.name.clone()))
+            .collect()
+    }
+
+    pub fn has_required_fields(&self, passport: &Passport) -> bool {
+        self.fields.iter().all(|field| !field.required || passport.get(&field.name).is_some())
+    }
+
+    // Every way the passport fails the schema, in the order the fields are
+    // given in the schema, stopping at the first rule each field fails.
+    // Fields the schema doesn't know about don't make a passport invalid.
+    pub fn validate(&self, passport: &Passport) -> Vec<Violation> {
+
+        let mut violations = Vec::new();
+
+        for field in &self.fields {
+            match passport.get(&field.name) {
+                Some(value) => {
+                    if let Some(violation) = field.rules.iter().find_map(|rule| rule.check(&field.name, value).err()) {
+                        violations.push(violation);
+                    }
+                }
+                None if field.required => violations.push(Violation::MissingField(field.name.clone())),
+                None => {}
+            }
+        }
+
+        violations
+    }
+
+    pub fn is_valid(&self, passport: &Passport) -> bool {
+        self.has_required_fields(passport) && self.fields.iter().all(|field| {
+            passport.get(&field.name).is_none_or(|value| field.rules.iter().all(|rule| rule.check(&field.name, value).is_ok()))
+        })
+    }
+
+    pub fn unknown_fields<'a>(&self, passport: &'a Passport) -> Vec<&'a super::Field> {
+        passport.fields.iter().filter(|f| self.field(&f.key).is_none()).collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Rule, Schema, Violation};
+    use crate::days::day04::parse_input;
+
+    #[test]
+    fn test_rules() {
+        let schema = Schema::puzzle();
+        let check = |field: &str, value: &str| schema.field(field).unwrap().rules.iter().all(|rule| rule.check(field, value).is_ok());
+
+        assert!(check("byr", "2002"));
+        assert!(!check("byr", "2003"));
+        assert!(!check("byr", "02002"));
+        assert!(check("hgt", "60in"));
+        assert!(check("hgt", "190cm"));
+        assert!(check("hgt", "1750mm"));
+        assert!(check("hgt", "5'11\""));
+        assert!(!check("hgt", "190in"));
+        assert!(!check("hgt", "429496880cm"));
+        assert!(!check("hgt", "190"));
+        assert!(!check("hgt", "170cmx"));
+        assert!(!check("hgt", "abc"));
+        assert!(check("hcl", "#123abc"));
+        assert!(!check("hcl", "#123abz"));
+        assert!(!check("hcl", "123abc"));
+        assert!(check("ecl", "brn"));
+        assert!(!check("ecl", "wat"));
+        assert!(check("pid", "000000001"));
+        assert!(!check("pid", "0123456789"));
+        assert!(check("cid", "anything"));
+        assert!(matches!(schema.field("hgt").unwrap().rules[0], Rule::Height(_)));
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = Schema::puzzle();
+        let passports = parse_input("byr:2007 iyr:2010 eyr:2030 hgt:190 hcl:123abz ecl:wat pid:0123456789\n\nhgt:80in").unwrap();
+
+        let violations = schema.validate(&passports[0]);
+        assert_eq!(violations[0], Violation::OutOfRange { field: String::from("byr"), value: 2007, range: 1920..=2002, unit: String::new() });
+        assert_eq!(violations.iter().map(|v| v.to_string()).collect::<Vec<_>>(), vec![
+            "byr 2007 is outside 1920..=2002",
+            "hgt '190' has no unit, expected one of mm, cm, in, ft'in\"",
+            "hcl '123abz' should be like ^#[0-9a-f]{6}$",
+            "ecl 'wat' should be one of amb, blu, brn, gry, grn, hzl, oth",
+            "pid '0123456789' should be like ^[0-9]{9}$",
+        ]);
+
+        let violations = schema.validate(&passports[1]);
+        assert_eq!(violations.len(), 7);
+        assert_eq!(violations[0], Violation::MissingField(String::from("byr")));
+        assert_eq!(violations[3].to_string(), "hgt 2032mm is outside 1499..=1930mm");
+        assert_eq!(violations[3].reason(), "hgt out of range");
+        assert_eq!(schema.missing_fields(&passports[1]).len(), 6);
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = Schema::parse(r#"{
+  "hgt": {"unit": {"cm": "150..=193"}},
+  "ecl": {"one_of": ["amb", "blu"], "required": false}
+}"#, true).unwrap();
+
+        let passports = parse_input("hgt:160cm\n\nhgt:160in ecl:amb\n\necl:blu\n\nhgt:170cm ecl:gry").unwrap();
+        let valid: Vec<bool> = passports.iter().map(|p| schema.is_valid(p)).collect();
+        assert_eq!(valid, vec![true, false, false, false]);
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        let schema = Schema::puzzle();
+        let passports = parse_input("byr:1937 iyr:2017 eyr:2020 hgt:183cm\nhcl:#fffffd ecl:gry pid:860033327 xyz:1").unwrap();
+
+        assert!(schema.is_valid(&passports[0]));
+        let unknown: Vec<(&str, usize)> = schema.unknown_fields(&passports[0]).iter().map(|f| (f.key.as_str(), f.line)).collect();
+        assert_eq!(unknown, vec![("xyz", 2)]);
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        for (schema, json) in &[
+            ("[byr]\nyear = \"2002..=1920\"", false),
+            ("[byr]\nyear = 1920", false),
+            ("[byr]\nshape = \"round\"", false),
+            ("[hcl]\nregex = \"(\"", false),
+            ("[cid]\nrequired = \"no\"", false),
+            ("byr = 1", false),
+            ("{\"ecl\": {\"one_of\": [1, 2]}}", true),
+            ("[1, 2]", true),
+        ] {
+            assert!(Schema::parse(schema, *json).is_err(), "{}", schema);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::parse_lines;
+use crate::{ParseError, SolveError, Solution};
+
+pub mod boarding_pass;
+pub mod seat_map;
+
+use boarding_pass::{BoardingPass, Cabin};
+use seat_map::SeatMap;
+
+fn parse_input(input: &str, cabin: Cabin) -> Result<Vec<BoardingPass>, ParseError> {
+
+    parse_lines(input, |line| BoardingPass::decode(cabin, line))
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum ReportMode {
+    #[default]
+    Off,
+    Gaps,
+    Map,
+}
+
+impl FromStr for ReportMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(ReportMode::Off),
+            "gaps" => Ok(ReportMode::Gaps),
+            "map" => Ok(ReportMode::Map),
+            unknown => Err(format!("unknown report '{}', expected off, gaps or map", unknown)),
+        }
+    }
+}
+
+// --report gaps lists every run of free seats and how full each row is,
+// and --report map draws the cabin as well. The report is given even when
+// part two has no answer, as that's when it's needed.
+#[derive(Default)]
+pub struct DayFive {
+    pub cabin: Cabin,
+    pub report: ReportMode,
+}
+
+impl Solution for DayFive {
+    const DAY: u32 = 5;
+
+    type Input = Vec<BoardingPass>;
+    type Output = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input, self.cabin)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part_one(input).ok_or_else(|| SolveError::new("there are no boarding passes"))
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        find_missing_seat(input).map_err(|e| SolveError::new(e.to_string()))
+    }
+
+    fn report(&self, input: &Self::Input) -> Result<Option<String>, SolveError> {
+
+        if self.report == ReportMode::Off {
+            return Ok(None)
+        }
+
+        let map = SeatMap::new(self.cabin, input);
+        let summary = map.summary();
+
+        Ok(Some(match self.report {
+            ReportMode::Map => format!("{}\n{}", map.render(), summary),
+            _ => summary,
+        }))
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+
+        let bits = || value.parse::<u32>().map_err(|e| format!("bad --{} '{}': {}", name, value, e));
+
+        match name {
+            "report" => self.report = value.parse()?,
+            "row-bits" => self.cabin = Cabin::new(bits()?, self.cabin.column_bits())?,
+            "column-bits" => self.cabin = Cabin::new(self.cabin.row_bits(), bits()?)?,
+            _ => return Err(format!("day 5 has no option --{}", name)),
+        }
+
+        Ok(())
+    }
+}
+
+fn part_one(passes: &[BoardingPass]) -> Option<u32> {
+
+    passes.iter().map(BoardingPass::seat_id).max()
+}
+
+// Why the missing seat couldn't be pinned down.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissingSeatError {
+    NoPasses,
+    Duplicate(u32),
+    NoneMissing,
+    Ambiguous(Vec<u32>),
+}
+
+impl fmt::Display for MissingSeatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MissingSeatError::NoPasses => write!(f, "there are no boarding passes"),
+            MissingSeatError::Duplicate(id) => write!(f, "seat {} is on more than one boarding pass", id),
+            MissingSeatError::NoneMissing => write!(f, "no seats are missing between the first and last taken seats"),
+            MissingSeatError::Ambiguous(candidates) => {
+                let candidates: Vec<String> = candidates.iter().map(|id| id.to_string()).collect();
+                write!(f, "{} seats are missing, so it could be any of {}", candidates.len(), candidates.join(", "))
+            }
+        }
+    }
+}
+
+// Our seat is the only one missing between the first and last taken seats,
+// as the ones missing at the front and back don't exist on this plane. The
+// seats in between are marked off in a bitset, so this is linear in the
+// number of passes plus the number of seats, and doesn't need them sorted.
+pub fn find_missing_seat(passes: &[BoardingPass]) -> Result<u32, MissingSeatError> {
+
+    let first = passes.iter().map(BoardingPass::seat_id).min().ok_or(MissingSeatError::NoPasses)?;
+    let last = passes.iter().map(BoardingPass::seat_id).max().unwrap();
+
+    let mut taken = vec![0u64; (last - first) as usize / 64 + 1];
+
+    for id in passes.iter().map(BoardingPass::seat_id) {
+        let (word, bit) = ((id - first) as usize / 64, (id - first) % 64);
+        if taken[word] >> bit & 1 == 1 {
+            return Err(MissingSeatError::Duplicate(id))
+        }
+        taken[word] |= 1 << bit;
+    }
+
+    let candidates: Vec<u32> = (first..=last)
+        .filter(|id| taken[(id - first) as usize / 64] >> ((id - first) % 64) & 1 == 0)
+        .collect();
+
+    match candidates.as_slice() {
+        [] => Err(MissingSeatError::NoneMissing),
+        [id] => Ok(*id),
+        _ => Err(MissingSeatError::Ambiguous(candidates)),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::boarding_pass::{BoardingPass, Cabin};
+    use super::{find_missing_seat, DayFive, MissingSeatError, ReportMode};
+    use crate::Solution;
+
+    #[test]
+    fn test_day_five_part_one() {
+        let day = DayFive::default();
+        let passes = day.parse("BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL").unwrap();
+
+        assert_eq!(day.part_one(&passes), Ok(820));
+        assert!(day.part_one(&Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_plane_options() {
+        let mut day = DayFive::default();
+        day.set_option("row-bits", "8").unwrap();
+        day.set_option("column-bits", "4").unwrap();
+
+        let passes = day.parse("BBBBBBBBRRRL\nFFFFFFFFLLLR").unwrap();
+        assert_eq!(day.part_one(&passes), Ok(4094));
+
+        assert!(day.parse("BFFFBBFRRR").is_err());
+        assert!(day.set_option("row-bits", "0").is_err());
+        assert!(day.set_option("column-bits", "x").is_err());
+        assert_eq!(day.set_option("seats", "x"), Err(String::from("day 5 has no option --seats")));
+    }
+
+    #[test]
+    fn test_day_five_part_two() {
+        let mut day = DayFive::default();
+        day.set_option("row-bits", "2").unwrap();
+        day.set_option("column-bits", "2").unwrap();
+
+        // Seats 0 to 15, with 0, 5 and 13 to 15 free.
+        let passes = day.parse("FFLR\nFFRL\nFFRR\nFBLL\nFBRL\nFBRR\nBFLL\nBFLR\nBFRL\nBFRR\nBBLL").unwrap();
+        assert_eq!(day.part_two(&passes), Ok(5));
+        assert!(day.part_two(&passes[..3].to_vec()).is_err());
+
+        day.report = ReportMode::Map;
+        assert_eq!(day.report(&passes[..3].to_vec()).unwrap().unwrap(), "0 .### 3/4
+1 .... 0/4
+2 .... 0/4
+3 .... 0/4
+front gaps: seat 0
+back gaps: seats 4..=15 (12 seats)
+interior gaps: none
+rows: 0 full, 3 empty, 1 partly full
+  row 0: 3 taken, 1 free");
+    }
+
+    #[test]
+    fn test_find_missing_seat() {
+        let passes = |ids: &[u32]| -> Vec<BoardingPass> {
+            ids.iter().map(|&id| BoardingPass::from_seat_id(Cabin::default(), id).unwrap()).collect()
+        };
+
+        assert_eq!(find_missing_seat(&passes(&[12, 9, 8, 11])), Ok(10));
+        assert_eq!(find_missing_seat(&passes(&(100..300).filter(|&id| id != 250).collect::<Vec<_>>())), Ok(250));
+        assert_eq!(find_missing_seat(&passes(&[])), Err(MissingSeatError::NoPasses));
+        assert_eq!(find_missing_seat(&passes(&[5, 6, 7])), Err(MissingSeatError::NoneMissing));
+        assert_eq!(find_missing_seat(&passes(&[5, 7, 5])), Err(MissingSeatError::Duplicate(5)));
+
+        let error = find_missing_seat(&passes(&[3, 5, 8])).unwrap_err();
+        assert_eq!(error, MissingSeatError::Ambiguous(vec![4, 6, 7]));
+        assert_eq!(error.to_string(), "3 seats are missing, so it could be any of 4, 6, 7");
+    }
+}
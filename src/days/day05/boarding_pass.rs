@@ -0,0 +1,224 @@
+use std::fmt;
+
+use crate::error::LineError;
+
+// The shape of the plane, as the number of bits a boarding pass uses for
+// the row and for the column. The puzzle's plane has 7 and 3, which is 128
+// rows of 8 seats.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cabin {
+    row_bits: u32,
+    column_bits: u32,
+}
+
+impl Default for Cabin {
+    fn default() -> Self {
+        Cabin { row_bits: 7, column_bits: 3 }
+    }
+}
+
+impl Cabin {
+    // Seat ids have to fit in a u32, with room to count one past the last.
+    pub fn new(row_bits: u32, column_bits: u32) -> Result<Self, String> {
+
+        if row_bits == 0 || column_bits == 0 {
+            return Err(String::from("a plane needs at least one bit for rows and one for columns"))
+        }
+
+        if row_bits + column_bits > 31 {
+            return Err(format!("{} row bits and {} column bits is too many seats, the most is 31 bits", row_bits, column_bits))
+        }
+
+        Ok(Cabin { row_bits, column_bits })
+    }
+
+    pub fn row_bits(&self) -> u32 {
+        self.row_bits
+    }
+
+    pub fn column_bits(&self) -> u32 {
+        self.column_bits
+    }
+
+    pub fn rows(&self) -> u32 {
+        1 << self.row_bits
+    }
+
+    pub fn columns(&self) -> u32 {
+        1 << self.column_bits
+    }
+
+    pub fn seats(&self) -> u32 {
+        self.rows() * self.columns()
+    }
+}
+
+// A seat, which can be turned into a seat id or a code like FBFBBFFRLR and
+// back again. Codes are the row then the column in binary, most significant
+// bit first, with F and L for 0 and B and R for 1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BoardingPass {
+    cabin: Cabin,
+    row: u32,
+    column: u32,
+}
+
+impl BoardingPass {
+    pub fn new(cabin: Cabin, row: u32, column: u32) -> Result<Self, String> {
+
+        if row >= cabin.rows() || column >= cabin.columns() {
+            return Err(format!("row {}, column {} isn't on a plane of {} rows of {}", row, column, cabin.rows(), cabin.columns()))
+        }
+
+        Ok(BoardingPass { cabin, row, column })
+    }
+
+    pub fn from_seat_id(cabin: Cabin, seat_id: u32) -> Result<Self, String> {
+
+        if seat_id >= cabin.seats() {
+            return Err(format!("seat id {} isn't on a plane of {} seats", seat_id, cabin.seats()))
+        }
+
+        Ok(BoardingPass { cabin, row: seat_id >> cabin.column_bits, column: seat_id & (cabin.columns() - 1) })
+    }
+
+    pub fn decode(cabin: Cabin, code: &str) -> Result<Self, LineError> {
+
+        if let Some((i, c)) = code.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(unexpected_char(i, c))
+        }
+
+        let length = (cabin.row_bits + cabin.column_bits) as usize;
+        if code.len() != length {
+            return Err(LineError::new(0, format!("boarding pass should be {} characters but is {}", length, code.len())))
+        }
+
+        let (row_str, col_str) = code.split_at(cabin.row_bits as usize);
+
+        let row = bin_str_to_int(row_str, 'F', 'B').map_err(|(i, c)| unexpected_char(i, c))?;
+        let column = bin_str_to_int(col_str, 'L', 'R').map_err(|(i, c)| unexpected_char(i + row_str.len(), c))?;
+
+        Ok(BoardingPass { cabin, row, column })
+    }
+
+    pub fn encode(&self) -> String {
+
+        let bits = |value: u32, width: u32, zero: char, one: char| {
+            (0..width).rev().map(move |bit| if value >> bit & 1 == 1 { one } else { zero })
+        };
+
+        bits(self.row, self.cabin.row_bits, 'F', 'B')
+            .chain(bits(self.column, self.cabin.column_bits, 'L', 'R'))
+            .collect()
+    }
+
+    pub fn cabin(&self) -> Cabin {
+        self.cabin
+    }
+
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    pub fn seat_id(&self) -> u32 {
+        self.row * self.cabin.columns() + self.column
+    }
+}
+
+impl fmt::Display for BoardingPass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+fn unexpected_char(index: usize, c: char) -> LineError {
+    LineError::new(index, format!("unexpected character '{}' in boarding pass", c))
+}
+
+// Read a string of two chars as binary. On failure, return the first
+// char that was neither, along with where it was.
+fn bin_str_to_int(bin_str: &str, zero: char, one: char) -> Result<u32, (usize, char)> {
+
+    bin_str.char_indices().try_fold(0, |acc, (i, c)| {
+        let bit = match c {
+            c if c == zero => 0,
+            c if c == one => 1,
+            _ => return Err((i, c)),
+        };
+        Ok((acc << 1) + bit)
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{BoardingPass, Cabin};
+
+    fn decode(code: &str) -> BoardingPass {
+        BoardingPass::decode(Cabin::default(), code).unwrap()
+    }
+
+    #[test]
+    fn test_decode() {
+        let pass = decode("FBFBBFFRLR");
+        assert_eq!((pass.row(), pass.column(), pass.seat_id()), (44, 5, 357));
+        assert_eq!(decode("BFFFBBFRRR").seat_id(), 567);
+        assert_eq!(decode("FFFBBBFRRR").seat_id(), 119);
+        assert_eq!(decode("BBFFBBFRLL").seat_id(), 820);
+
+        let wide = Cabin::new(8, 4).unwrap();
+        let pass = BoardingPass::decode(wide, "BBBBBBBBRRRL").unwrap();
+        assert_eq!((pass.row(), pass.column(), pass.seat_id()), (255, 14, 4094));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let cabin = Cabin::default();
+
+        assert_eq!(BoardingPass::decode(cabin, "BFFFBBFRRX").unwrap_err().column, 9);
+        assert_eq!(BoardingPass::decode(cabin, "BFFFRBFRRR").unwrap_err().column, 4);
+        assert_eq!(BoardingPass::decode(cabin, "BFFFÉFRRR").unwrap_err().column, 4);
+        assert!(BoardingPass::decode(cabin, "BFFFBBFRR").is_err());
+        assert!(BoardingPass::decode(Cabin::new(8, 3).unwrap(), "BFFFBBFRRR").is_err());
+    }
+
+    #[test]
+    fn test_bad_seats() {
+        let cabin = Cabin::default();
+
+        assert!(BoardingPass::new(cabin, 128, 0).is_err());
+        assert!(BoardingPass::new(cabin, 0, 8).is_err());
+        assert!(BoardingPass::from_seat_id(cabin, 1024).is_err());
+        assert!(Cabin::new(0, 3).is_err());
+        assert!(Cabin::new(20, 12).is_err());
+    }
+
+    // Every seat on planes of a few different shapes makes it from a seat id
+    // to a code and back, and from a row and column to a seat id and back.
+    #[test]
+    fn test_round_trips() {
+        for &(row_bits, column_bits) in &[(7, 3), (8, 3), (7, 4), (1, 1), (10, 6), (3, 9)] {
+            let cabin = Cabin::new(row_bits, column_bits).unwrap();
+
+            for seat_id in 0..cabin.seats() {
+                let pass = BoardingPass::from_seat_id(cabin, seat_id).unwrap();
+                let code = pass.encode();
+
+                assert_eq!(code.len() as u32, row_bits + column_bits);
+                assert_eq!(BoardingPass::decode(cabin, &code), Ok(pass));
+                assert_eq!(pass.seat_id(), seat_id);
+                assert_eq!(BoardingPass::new(cabin, pass.row(), pass.column()), Ok(pass));
+            }
+        }
+
+        let cabin = Cabin::new(16, 15).unwrap();
+        for &seat_id in &[0, 1, 12_345_678, cabin.seats() - 1] {
+            let pass = BoardingPass::from_seat_id(cabin, seat_id).unwrap();
+            assert_eq!(BoardingPass::decode(cabin, &pass.to_string()).unwrap().seat_id(), seat_id);
+        }
+    }
+}
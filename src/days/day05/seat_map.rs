@@ -0,0 +1,198 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::grid::Grid;
+
+use super::boarding_pass::{BoardingPass, Cabin};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Seat {
+    Free,
+    Taken,
+}
+
+// Where a run of free seats is. Seats at the very front and back of the
+// plane don't exist on this flight, so only interior gaps can be ours.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Place {
+    Front,
+    Back,
+    Interior,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gap {
+    pub place: Place,
+    pub seat_ids: RangeInclusive<u32>,
+}
+
+impl Gap {
+    pub fn size(&self) -> u32 {
+        self.seat_ids.end() - self.seat_ids.start() + 1
+    }
+}
+
+impl fmt::Display for Gap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.size() {
+            1 => write!(f, "seat {}", self.seat_ids.start()),
+            n => write!(f, "seats {}..={} ({} seats)", self.seat_ids.start(), self.seat_ids.end(), n),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RowStats {
+    pub row: u32,
+    pub taken: u32,
+    pub free: u32,
+}
+
+// The cabin row by row, with a seat taken for every boarding pass.
+pub struct SeatMap {
+    seats: Grid<Seat>,
+}
+
+impl SeatMap {
+    pub fn new(cabin: Cabin, passes: &[BoardingPass]) -> Self {
+
+        let mut seats = Grid::new(cabin.columns() as usize, cabin.rows() as usize, vec![Seat::Free; cabin.seats() as usize]);
+
+        for pass in passes {
+            seats[(pass.column() as usize, pass.row() as usize)] = Seat::Taken;
+        }
+
+        SeatMap { seats }
+    }
+
+    // Seats in seat id order, which is the same as row by row.
+    fn seat_ids(&self) -> impl Iterator<Item = (u32, Seat)> + '_ {
+        self.seats.rows().flatten().enumerate().map(|(id, &seat)| (id as u32, seat))
+    }
+
+    // Every run of free seats, front to back.
+    pub fn gaps(&self) -> Vec<Gap> {
+
+        let last = (self.seats.width() * self.seats.height()) as u32 - 1;
+        let mut gaps = Vec::new();
+        let mut start = None;
+
+        for (id, seat) in self.seat_ids() {
+            match (seat, start) {
+                (Seat::Free, None) => start = Some(id),
+                (Seat::Taken, Some(first)) => {
+                    gaps.push(Gap { place: if first == 0 { Place::Front } else { Place::Interior }, seat_ids: first..=id - 1 });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        // A plane with nobody on it is all front.
+        if let Some(first) = start {
+            gaps.push(Gap { place: if first == 0 { Place::Front } else { Place::Back }, seat_ids: first..=last });
+        }
+
+        gaps
+    }
+
+    pub fn row_stats(&self) -> Vec<RowStats> {
+        self.seats.rows()
+            .enumerate()
+            .map(|(row, seats)| {
+                let taken = seats.iter().filter(|&&seat| seat == Seat::Taken).count() as u32;
+                RowStats { row: row as u32, taken, free: seats.len() as u32 - taken }
+            })
+            .collect()
+    }
+
+    // The cabin from the front, with # for taken seats and . for free ones,
+    // and how full each row is.
+    pub fn render(&self) -> String {
+
+        let width = (self.seats.height() - 1).to_string().len();
+
+        self.seats.rows()
+            .zip(self.row_stats())
+            .map(|(seats, stats)| {
+                let seats: String = seats.iter().map(|&seat| if seat == Seat::Taken { '#' } else { '.' }).collect();
+                format!("{:>width$} {} {}/{}", stats.row, seats, stats.taken, stats.taken + stats.free, width = width)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // The gaps, and how full the rows are. Rows that are only partly full
+    // are listed, since those are the ones a manifest check cares about.
+    pub fn summary(&self) -> String {
+
+        let mut lines = Vec::new();
+        let gaps = self.gaps();
+
+        for (place, name) in &[(Place::Front, "front"), (Place::Back, "back"), (Place::Interior, "interior")] {
+            let found: Vec<String> = gaps.iter().filter(|gap| gap.place == *place).map(|gap| gap.to_string()).collect();
+            if found.is_empty() {
+                lines.push(format!("{} gaps: none", name));
+            }
+            else {
+                lines.push(format!("{} gaps: {}", name, found.join(", ")));
+            }
+        }
+
+        let stats = self.row_stats();
+        let count = |f: &dyn Fn(&RowStats) -> bool| stats.iter().filter(|s| f(s)).count();
+
+        lines.push(format!("rows: {} full, {} empty, {} partly full",
+            count(&|s| s.free == 0), count(&|s| s.taken == 0), count(&|s| s.free > 0 && s.taken > 0)));
+
+        for row in stats.iter().filter(|s| s.free > 0 && s.taken > 0) {
+            lines.push(format!("  row {}: {} taken, {} free", row.row, row.taken, row.free));
+        }
+
+        lines.join("\n")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Gap, Place, SeatMap};
+    use crate::days::day05::boarding_pass::{BoardingPass, Cabin};
+
+    fn seat_map(cabin: Cabin, seat_ids: &[u32]) -> SeatMap {
+        let passes: Vec<_> = seat_ids.iter().map(|&id| BoardingPass::from_seat_id(cabin, id).unwrap()).collect();
+        SeatMap::new(cabin, &passes)
+    }
+
+    #[test]
+    fn test_gaps() {
+        let cabin = Cabin::new(2, 2).unwrap();
+        let map = seat_map(cabin, &[2, 3, 4, 6, 9, 10, 11, 13]);
+
+        assert_eq!(map.gaps(), vec![
+            Gap { place: Place::Front, seat_ids: 0..=1 },
+            Gap { place: Place::Interior, seat_ids: 5..=5 },
+            Gap { place: Place::Interior, seat_ids: 7..=8 },
+            Gap { place: Place::Interior, seat_ids: 12..=12 },
+            Gap { place: Place::Back, seat_ids: 14..=15 },
+        ]);
+
+        assert_eq!(seat_map(cabin, &[]).gaps(), vec![Gap { place: Place::Front, seat_ids: 0..=15 }]);
+        assert!(seat_map(cabin, &(0..16).collect::<Vec<_>>()).gaps().is_empty());
+    }
+
+    #[test]
+    fn test_render_and_summary() {
+        let cabin = Cabin::new(2, 2).unwrap();
+        let map = seat_map(cabin, &[2, 3, 4, 6, 9, 10, 11]);
+
+        assert_eq!(map.render(), "0 ..## 2/4\n1 #.#. 2/4\n2 .### 3/4\n3 .... 0/4");
+        assert_eq!(map.summary(), "front gaps: seats 0..=1 (2 seats)
+back gaps: seats 12..=15 (4 seats)
+interior gaps: seat 5, seats 7..=8 (2 seats)
+rows: 0 full, 1 empty, 3 partly full
+  row 0: 2 taken, 2 free
+  row 1: 2 taken, 2 free
+  row 2: 3 taken, 1 free");
+    }
+}
@@ -0,0 +1,242 @@
+use std::str::FromStr;
+
+use crate::error::LineError;
+use crate::records::{split_records, Record};
+use crate::sets::{self, Answers, Set};
+use crate::{ParseError, SolveError, Solution};
+
+pub mod survey;
+
+use survey::Survey;
+
+fn parse_input(input: &str) -> Result<Vec<GroupAnswers>, ParseError> {
+
+    split_records(input).map(|record| parse_group_answers(&record)).collect()
+}
+
+fn parse_group_answers(record: &Record) -> Result<GroupAnswers, ParseError> {
+
+    let people = record.numbered_lines()
+        .map(|(number, answers)| parse_answers(answers).map_err(|e| ParseError::new(number, answers, e)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(GroupAnswers { first_line: record.first_line, people })
+}
+
+fn parse_answers(answers: &str) -> Result<Answers, LineError> {
+
+    answers.char_indices()
+        .map(|(i, c)| match c {
+            'a'..='z' => Ok(c),
+            _ => Err(LineError::new(i, format!("'{}' is not a question between a and z", c))),
+        })
+        .collect()
+}
+
+// What everyone in a group answered, one person to a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupAnswers {
+    pub first_line: usize,
+    pub people: Vec<Answers>,
+}
+
+// How the answers of everyone in a group are put together before they're
+// counted. Part one counts the union and part two the intersection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Aggregate {
+    Union,
+    Intersection,
+    SymmetricDifference,
+    AtLeast(usize),
+}
+
+impl Aggregate {
+    pub fn apply(self, group: &[Answers]) -> Answers {
+        match self {
+            Aggregate::Union => sets::union(group),
+            Aggregate::Intersection => sets::intersection(group),
+            Aggregate::SymmetricDifference => sets::symmetric_difference(group),
+            Aggregate::AtLeast(k) => sets::at_least(group, k),
+        }
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "union" => Ok(Aggregate::Union),
+            None if s == "intersection" => Ok(Aggregate::Intersection),
+            None if s == "symmetric-difference" => Ok(Aggregate::SymmetricDifference),
+            Some(("at-least", k)) => match k.parse::<usize>() {
+                Ok(k) if k > 0 => Ok(Aggregate::AtLeast(k)),
+                _ => Err(format!("at-least needs a number of people above 0, not '{}'", k)),
+            },
+            _ => Err(format!("unknown aggregate '{}', expected union, intersection, symmetric-difference or at-least:K", s)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum ReportMode {
+    #[default]
+    Off,
+    Text,
+    Csv,
+}
+
+impl FromStr for ReportMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(ReportMode::Off),
+            "text" => Ok(ReportMode::Text),
+            "csv" => Ok(ReportMode::Csv),
+            unknown => Err(format!("unknown report '{}', expected off, text or csv", unknown)),
+        }
+    }
+}
+
+// --part-one and --part-two change how each part puts a group's answers
+// together, e.g. --part-two at-least:2 counts the questions at least two
+// people in each group answered.
+//
+// --report text or --report csv gives a survey of every question in the
+// report.
+pub struct DaySix {
+    pub part_one: Aggregate,
+    pub part_two: Aggregate,
+    pub report: ReportMode,
+}
+
+impl Default for DaySix {
+    fn default() -> Self {
+        DaySix { part_one: Aggregate::Union, part_two: Aggregate::Intersection, report: ReportMode::Off }
+    }
+}
+
+impl Solution for DaySix {
+    const DAY: u32 = 6;
+
+    type Input = Vec<GroupAnswers>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(count(input, self.part_one))
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(count(input, self.part_two))
+    }
+
+    fn report(&self, input: &Self::Input) -> Result<Option<String>, SolveError> {
+        Ok(match self.report {
+            ReportMode::Off => None,
+            ReportMode::Text => Some(Survey::new(input).to_text()),
+            ReportMode::Csv => Some(Survey::new(input).to_csv()),
+        })
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+
+        match name {
+            "part-one" => self.part_one = value.parse()?,
+            "part-two" => self.part_two = value.parse()?,
+            "report" => self.report = value.parse()?,
+            _ => return Err(format!("day 6 has no option --{}", name)),
+        }
+
+        Ok(())
+    }
+}
+
+fn count(group_answers: &[GroupAnswers], aggregate: Aggregate) -> usize {
+
+    group_answers.iter().map(|group| aggregate.apply(&group.people).len()).sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::survey::Survey;
+    use super::{count, parse_input, Aggregate, DaySix, ReportMode};
+    use crate::Solution;
+
+    #[test]
+    fn test_day_six_part_one() {
+        let example_input = String::from("abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b");
+
+        assert_eq!(count(&parse_input(&example_input).unwrap(), Aggregate::Union), 11);
+    }
+
+    #[test]
+    fn test_day_six_part_two() {
+        let example_input = String::from("abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b");
+
+        assert_eq!(count(&parse_input(&example_input).unwrap(), Aggregate::Intersection), 6);
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let groups = parse_input("abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb").unwrap();
+
+        // abc, abc, bc, nothing and b.
+        assert_eq!(count(&groups, Aggregate::SymmetricDifference), 9);
+        // Only a in the third and fourth groups.
+        assert_eq!(count(&groups, Aggregate::AtLeast(2)), 2);
+
+        let mut day = DaySix::default();
+        day.set_option("part-two", "at-least:1").unwrap();
+        assert_eq!(day.part_two(&groups), Ok(11));
+
+        assert_eq!("symmetric-difference".parse(), Ok(Aggregate::SymmetricDifference));
+        assert!("at-least:0".parse::<Aggregate>().is_err());
+        assert!("at-least".parse::<Aggregate>().is_err());
+        assert!(day.set_option("part-one", "difference").is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let groups = parse_input("abc\n\na\nb\nc\n\nab\nac").unwrap();
+
+        assert_eq!(DaySix::default().report(&groups), Ok(None));
+
+        let day = DaySix { report: ReportMode::Csv, ..DaySix::default() };
+        assert_eq!(day.part_two(&groups), Ok(4));
+        assert_eq!(day.report(&groups), Ok(Some(Survey::new(&groups).to_csv())));
+    }
+}
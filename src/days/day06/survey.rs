@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+
+use crate::sets::{self, Answers, Set};
+use crate::table::format_table;
+
+use super::GroupAnswers;
+
+const HISTOGRAM_WIDTH: usize = 40;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct QuestionStats {
+    pub question: char,
+    // How many people answered yes.
+    pub people: usize,
+    // How many groups had anyone answer yes.
+    pub groups: usize,
+    // How many groups had everyone answer yes.
+    pub unanimous: usize,
+}
+
+// Everything the customs forms say, rather than just the two totals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Survey {
+    pub people: usize,
+    pub groups: usize,
+    pub questions: Vec<QuestionStats>,
+    // How many groups there are of each size.
+    pub group_sizes: BTreeMap<usize, usize>,
+    // The first lines of groups of more than one person that didn't all
+    // answer yes to any one question.
+    pub no_agreement: Vec<usize>,
+}
+
+impl Survey {
+    pub fn new(groups: &[GroupAnswers]) -> Self {
+
+        let mut questions: Vec<QuestionStats> = ('a'..='z')
+            .map(|question| QuestionStats { question, ..QuestionStats::default() })
+            .collect();
+
+        let mut group_sizes = BTreeMap::new();
+        let mut no_agreement = Vec::new();
+
+        for group in groups {
+            let anyone = sets::union(&group.people);
+            let everyone = sets::intersection(&group.people);
+
+            for stats in &mut questions {
+                stats.people += group.people.iter().filter(|person| person.contains(stats.question)).count();
+                stats.groups += anyone.contains(stats.question) as usize;
+                stats.unanimous += everyone.contains(stats.question) as usize;
+            }
+
+            *group_sizes.entry(group.people.len()).or_insert(0) += 1;
+
+            if group.people.len() > 1 && everyone.is_empty() {
+                no_agreement.push(group.first_line);
+            }
+        }
+
+        Survey {
+            people: groups.iter().map(|group| group.people.len()).sum(),
+            groups: groups.len(),
+            questions,
+            group_sizes,
+            no_agreement,
+        }
+    }
+
+    // The questions the most groups were unanimous about, and the fewest,
+    // out of those anyone answered at all. Ties are all given.
+    pub fn most_and_least_unanimous(&self) -> Option<(Answers, Answers)> {
+
+        let answered: Vec<&QuestionStats> = self.questions.iter().filter(|stats| stats.groups > 0).collect();
+
+        let most = answered.iter().map(|stats| stats.unanimous).max()?;
+        let least = answered.iter().map(|stats| stats.unanimous).min()?;
+        let with = |count: usize| answered.iter().filter(|s| s.unanimous == count).map(|s| s.question).collect();
+
+        Some((with(most), with(least)))
+    }
+
+    pub fn to_text(&self) -> String {
+
+        let mut sections = vec![format!("{} people in {} groups", self.people, self.groups)];
+
+        let rows: Vec<Vec<String>> = self.questions.iter()
+            .map(|s| vec![s.question.to_string(), s.people.to_string(), s.groups.to_string(), s.unanimous.to_string()])
+            .collect();
+        sections.push(format_table(&["question", "people", "groups", "unanimous"], &rows));
+
+        // Bars are scaled to fit, but anything there at all gets one #.
+        let largest = self.group_sizes.values().copied().max().unwrap_or(0);
+        let rows: Vec<Vec<String>> = self.group_sizes.iter()
+            .map(|(size, &count)| {
+                let bar = (count * HISTOGRAM_WIDTH).div_ceil(largest.max(1));
+                vec![size.to_string(), count.to_string(), "#".repeat(bar)]
+            })
+            .collect();
+        sections.push(format_table(&["group size", "groups", ""], &rows));
+
+        if let Some((most, least)) = self.most_and_least_unanimous() {
+            let unanimous = |questions: Answers| {
+                let count = self.questions.iter().find(|s| questions.contains(s.question)).map_or(0, |s| s.unanimous);
+                format!("{} ({} {})", questions, count, if count == 1 { "group" } else { "groups" })
+            };
+            sections.push(format!("most unanimous: {}\nleast unanimous: {}", unanimous(most), unanimous(least)));
+        }
+
+        let lines: Vec<String> = self.no_agreement.iter().map(|line| line.to_string()).collect();
+        sections.push(match lines.len() {
+            0 => String::from("groups with no agreement: none"),
+            1 => format!("groups with no agreement: 1, on line {}", lines[0]),
+            n => format!("groups with no agreement: {}, on lines {}", n, lines.join(", ")),
+        });
+
+        sections.join("\n\n")
+    }
+
+    // One table per section, with a blank line between them.
+    pub fn to_csv(&self) -> String {
+
+        let mut questions = vec![String::from("question,people,groups,unanimous")];
+        questions.extend(self.questions.iter()
+            .map(|s| format!("{},{},{},{}", s.question, s.people, s.groups, s.unanimous)));
+
+        let mut sizes = vec![String::from("group_size,groups")];
+        sizes.extend(self.group_sizes.iter().map(|(size, count)| format!("{},{}", size, count)));
+
+        let mut no_agreement = vec![String::from("no_agreement_line")];
+        no_agreement.extend(self.no_agreement.iter().map(|line| line.to_string()));
+
+        [questions, sizes, no_agreement].iter()
+            .map(|table| table.join("\n"))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Survey;
+    use crate::days::day06::parse_input;
+
+    const EXAMPLE_INPUT: &str = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+
+    #[test]
+    fn test_survey() {
+        let survey = Survey::new(&parse_input(EXAMPLE_INPUT).unwrap());
+
+        assert_eq!((survey.people, survey.groups), (11, 5));
+        assert_eq!(survey.questions[0].people, 8);
+        assert_eq!(survey.questions[0].groups, 4);
+        assert_eq!(survey.questions[0].unanimous, 3);
+        assert_eq!(survey.group_sizes.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>(), vec![(1, 2), (2, 1), (3, 1), (4, 1)]);
+        assert_eq!(survey.no_agreement, vec![3]);
+
+        let (most, least) = survey.most_and_least_unanimous().unwrap();
+        assert_eq!((most.to_string(), least.to_string()), (String::from("a"), String::from("c")));
+    }
+
+    #[test]
+    fn test_survey_output() {
+        let survey = Survey::new(&parse_input("ab\nb\n\nc\nd").unwrap());
+
+        assert!(survey.to_text().ends_with("most unanimous: b (1 group)\nleast unanimous: acd (0 groups)\n\ngroups with no agreement: 1, on line 4"));
+        assert_eq!(survey.to_csv().lines().take(3).collect::<Vec<_>>(), vec!["question,people,groups,unanimous", "a,1,1,0", "b,2,1,1"]);
+        assert!(survey.to_csv().ends_with("group_size,groups\n2,2\n\nno_agreement_line\n4"));
+    }
+}
@@ -0,0 +1,171 @@
+use regex::Regex;
+
+use crate::error::{parse_numbered_lines, LineError};
+use crate::{ParseError, SolveError, Solution};
+
+pub mod bag_graph;
+
+use bag_graph::BagGraph;
+
+struct Rule {
+    container: String,
+    contents: Vec<(String, u64)>,
+}
+
+fn parse_input(input: &str) -> Result<BagGraph, ParseError> {
+
+    let rules = parse_numbered_lines(input, parse_rule)?;
+
+    let mut graph = BagGraph::new();
+    let mut rule_lines = Vec::new();
+
+    for (line, rule) in rules {
+        let container = graph.intern(&rule.container);
+
+        // A second rule for the same colour would have one of them ignored.
+        rule_lines.resize(graph.len(), None);
+        if let Some(first) = rule_lines[container] {
+            let text = input.lines().nth(line - 1).unwrap_or("");
+            let reason = format!("there's already a rule for {} bags on line {}", rule.container, first);
+            return Err(ParseError::new(line, text, LineError::new(0, reason)))
+        }
+        rule_lines[container] = Some(line);
+
+        for (colour, count) in rule.contents {
+            let contained = graph.intern(&colour);
+            graph.add_edge(container, contained, count);
+        }
+    }
+
+    Ok(graph)
+}
+
+fn parse_rule(rule: &str) -> Result<Rule, LineError> {
+
+    lazy_static! {
+        static ref CONTAINER_NAME_REGEX: Regex = Regex::new(r"^((?P<container>[a-z]+ [a-z]+) bags?)").unwrap();
+        static ref COLOUR_NAME_REGEX: Regex = Regex::new(r"((?P<number>[1-9][0-9]*) (?P<colour>[a-z]+ [a-z]+) bags?)").unwrap();
+    }
+
+    let container = CONTAINER_NAME_REGEX.captures(rule)
+        .ok_or_else(|| LineError::new(0, "expected the rule to start with a bag colour like 'light red bags'"))?
+        .name("container").unwrap().as_str();
+
+    let contents = COLOUR_NAME_REGEX.captures_iter(rule)
+        .map(|capture| {
+            let number = capture.name("number").unwrap();
+            let count = number.as_str().parse::<u64>()
+                .map_err(|e| LineError::new(number.start(), format!("bad number of bags '{}': {}", number.as_str(), e)))?;
+            Ok((capture.name("colour").unwrap().as_str().to_string(), count))
+        })
+        .collect::<Result<_, LineError>>()?;
+
+    Ok(Rule { container: container.to_string(), contents })
+}
+
+const MY_BAG: &str = "shiny gold";
+
+#[derive(Default)]
+pub struct DaySeven;
+
+impl Solution for DaySeven {
+    const DAY: u32 = 7;
+
+    type Input = BagGraph;
+    type Output = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part_two(input)
+    }
+}
+
+fn my_bag(graph: &BagGraph) -> Result<usize, SolveError> {
+
+    graph.id(MY_BAG).ok_or_else(|| SolveError::new(format!("there are no rules about {} bags", MY_BAG)))
+}
+
+fn part_one(graph: &BagGraph) -> Result<u64, SolveError> {
+
+    Ok(graph.ancestors(my_bag(graph)?).len() as u64)
+}
+
+fn part_two(graph: &BagGraph) -> Result<u64, SolveError> {
+
+    graph.total_contents(my_bag(graph)?).map_err(|e| SolveError::new(e.to_string()))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::parse_input;
+    use super::part_one;
+    use super::part_two;
+
+    #[test]
+    fn test_day_seven_part_one() {
+let example_input = String::from("light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.");
+
+        assert_eq!(part_one(&parse_input(&example_input).unwrap()), Ok(4));
+    }
+
+    #[test]
+    fn test_day_seven_part_two() {
+        let example_input = String::from("light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.");
+
+        let example_input_two = String::from("shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.");
+
+        assert_eq!(part_two(&parse_input(&example_input).unwrap()), Ok(32));
+        assert_eq!(part_two(&parse_input(&example_input_two).unwrap()), Ok(126));
+    }
+
+    #[test]
+    fn test_duplicates_and_cycles() {
+        let graph = parse_input("shiny gold bags contain 1 dark red bag, 2 dark red bags.
+dark red bags contain 3 faded blue bags.
+faded blue bags contain no other bags.").unwrap();
+        assert_eq!(part_two(&graph), Ok(12));
+
+        let error = parse_input("shiny gold bags contain 1 dark red bag.
+dark red bags contain no other bags.
+shiny gold bags contain 2 faded blue bags.").unwrap_err();
+        assert_eq!(error.line, 3);
+
+        let graph = parse_input("shiny gold bags contain 1 dark red bag.
+dark red bags contain 12 faded blue bags.
+faded blue bags contain 1 dark red bag.").unwrap();
+        assert_eq!(part_one(&graph), Ok(0));
+        assert_eq!(part_two(&graph).unwrap_err().reason, "bags end up inside themselves: dark red -> faded blue -> dark red");
+
+        assert!(part_one(&parse_input("dark red bags contain no other bags.").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+// Colours are interned, so the graph itself only deals in these.
+pub type BagId = usize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BagGraphError {
+    // A bag that ends up inside itself, as the colours going round, with
+    // the first repeated at the end.
+    Cycle(Vec<String>),
+    // A bag holding more bags than can be counted.
+    Overflow(String),
+}
+
+impl fmt::Display for BagGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagGraphError::Cycle(path) => write!(f, "bags end up inside themselves: {}", path.join(" -> ")),
+            BagGraphError::Overflow(colour) => write!(f, "{} bags hold too many bags to count", colour),
+        }
+    }
+}
+
+// Which bags go directly inside which, and how many of them. Each edge is
+// kept as it was given, so a rule naming the same colour twice counts both.
+#[derive(Debug, Clone, Default)]
+pub struct BagGraph {
+    colours: Vec<String>,
+    ids: HashMap<String, BagId>,
+    contents: Vec<Vec<(BagId, u64)>>,
+    containers: Vec<Vec<BagId>>,
+    memo: OnceLock<Memo>,
+}
+
+// The answers to each bag's queries once they've been asked, which are
+// thrown away whenever the graph changes.
+#[derive(Debug, Clone)]
+struct Memo {
+    ancestors: Vec<OnceLock<Vec<BagId>>>,
+    descendants: Vec<OnceLock<Vec<BagId>>>,
+}
+
+impl Memo {
+    fn new(len: usize) -> Self {
+        Memo {
+            ancestors: vec![OnceLock::new(); len],
+            descendants: vec![OnceLock::new(); len],
+        }
+    }
+}
+
+// What's been memoised doesn't change which graph it is.
+impl PartialEq for BagGraph {
+    fn eq(&self, other: &Self) -> bool {
+        self.colours == other.colours && self.contents == other.contents
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Visit {
+    New,
+    Open,
+    Done,
+}
+
+impl BagGraph {
+    pub fn new() -> Self {
+        BagGraph::default()
+    }
+
+    pub fn intern(&mut self, colour: &str) -> BagId {
+
+        if let Some(&id) = self.ids.get(colour) {
+            return id
+        }
+
+        self.memo.take();
+
+        let id = self.colours.len();
+        self.colours.push(colour.to_string());
+        self.ids.insert(colour.to_string(), id);
+        self.contents.push(Vec::new());
+        self.containers.push(Vec::new());
+        id
+    }
+
+    pub fn add_edge(&mut self, container: BagId, contained: BagId, count: u64) {
+        self.memo.take();
+        self.contents[container].push((contained, count));
+        self.containers[contained].push(container);
+    }
+
+    pub fn id(&self, colour: &str) -> Option<BagId> {
+        self.ids.get(colour).copied()
+    }
+
+    pub fn colour(&self, id: BagId) -> &str {
+        &self.colours[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.colours.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colours.is_empty()
+    }
+
+    pub fn contents(&self, id: BagId) -> &[(BagId, u64)] {
+        &self.contents[id]
+    }
+
+    pub fn containers(&self, id: BagId) -> &[BagId] {
+        &self.containers[id]
+    }
+
+    fn memo(&self) -> &Memo {
+        self.memo.get_or_init(|| Memo::new(self.len()))
+    }
+
+    // Every bag reachable from the start by following the given edges, not
+    // counting the start itself unless it's on a cycle. Done without
+    // recursion, as rule sets can be deep, and each bag and edge is only
+    // looked at once.
+    fn reachable<I: Iterator<Item = BagId>>(&self, start: BagId, next: impl Fn(BagId) -> I) -> Vec<BagId> {
+
+        let mut seen = vec![false; self.len()];
+        let mut stack: Vec<BagId> = next(start).collect();
+        let mut found = Vec::new();
+
+        while let Some(id) = stack.pop() {
+            if !seen[id] {
+                seen[id] = true;
+                found.push(id);
+                stack.extend(next(id));
+            }
+        }
+
+        found.sort_unstable();
+        found
+    }
+
+    // The bags that can end up, however deep, holding this one. Worked out
+    // the first time it's asked for and memoised after that.
+    pub fn ancestors(&self, id: BagId) -> &[BagId] {
+        self.memo().ancestors[id]
+            .get_or_init(|| self.reachable(id, |id| self.containers[id].iter().copied()))
+    }
+
+    // The bags that can end up, however deep, inside this one, memoised in
+    // the same way.
+    pub fn descendants(&self, id: BagId) -> &[BagId] {
+        self.memo().descendants[id]
+            .get_or_init(|| self.reachable(id, |id| self.contents[id].iter().map(|&(inner, _)| inner)))
+    }
+
+    fn cycle(&self, stack: &[(BagId, usize)], back_to: BagId) -> BagGraphError {
+
+        let start = stack.iter().position(|&(id, _)| id == back_to).unwrap();
+        let mut path: Vec<String> = stack[start..].iter().map(|&(id, _)| self.colours[id].clone()).collect();
+        path.push(self.colours[back_to].clone());
+
+        BagGraphError::Cycle(path)
+    }
+
+    // A depth first walk from the given bags that calls done on each bag
+    // once everything inside it has been done, and fails on the first cycle.
+    fn post_order(&self, starts: impl Iterator<Item = BagId>, mut done: impl FnMut(BagId) -> Result<(), BagGraphError>) -> Result<(), BagGraphError> {
+
+        let mut visits = vec![Visit::New; self.len()];
+
+        for start in starts {
+            if visits[start] != Visit::New {
+                continue
+            }
+
+            visits[start] = Visit::Open;
+            let mut stack = vec![(start, 0)];
+
+            while let Some(&mut (id, ref mut edge)) = stack.last_mut() {
+                match self.contents[id].get(*edge) {
+                    Some(&(inner, _)) => {
+                        *edge += 1;
+                        match visits[inner] {
+                            Visit::New => {
+                                visits[inner] = Visit::Open;
+                                stack.push((inner, 0));
+                            }
+                            Visit::Open => return Err(self.cycle(&stack, inner)),
+                            Visit::Done => {}
+                        }
+                    }
+                    None => {
+                        visits[id] = Visit::Done;
+                        stack.pop();
+                        done(id)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Every bag, with each one before any bag it can go inside.
+    pub fn topological_order(&self) -> Result<Vec<BagId>, BagGraphError> {
+
+        let mut order = Vec::with_capacity(self.len());
+        self.post_order(0..self.len(), |id| {
+            order.push(id);
+            Ok(())
+        })?;
+
+        order.reverse();
+        Ok(order)
+    }
+
+    // How many bags this one holds in total. Each bag inside it is only
+    // counted up once, however many ways it's reached, so this is linear in
+    // the size of the graph. Only cycles this bag can reach are a problem.
+    pub fn total_contents(&self, id: BagId) -> Result<u64, BagGraphError> {
+
+        let mut totals: Vec<u64> = vec![0; self.len()];
+
+        self.post_order(std::iter::once(id), |bag| {
+            let total = self.contents[bag].iter().try_fold(0u64, |sum, &(inner, count)| {
+                totals[inner].checked_add(1)
+                    .and_then(|each| each.checked_mul(count))
+                    .and_then(|bags| sum.checked_add(bags))
+            });
+
+            totals[bag] = total.ok_or_else(|| BagGraphError::Overflow(self.colours[bag].clone()))?;
+            Ok(())
+        })?;
+
+        Ok(totals[id])
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{BagGraph, BagGraphError};
+
+    fn graph(edges: &[(&str, &str, u64)]) -> BagGraph {
+        let mut graph = BagGraph::new();
+        for &(outer, inner, count) in edges {
+            let (outer, inner) = (graph.intern(outer), graph.intern(inner));
+            graph.add_edge(outer, inner, count);
+        }
+        graph
+    }
+
+    fn colours<'a>(graph: &'a BagGraph, ids: &[usize]) -> Vec<&'a str> {
+        ids.iter().map(|&id| graph.colour(id)).collect()
+    }
+
+    #[test]
+    fn test_queries() {
+        let graph = graph(&[("a", "b", 2), ("a", "c", 1), ("b", "d", 3), ("c", "d", 4), ("b", "d", 1)]);
+        let (a, d) = (graph.id("a").unwrap(), graph.id("d").unwrap());
+
+        assert_eq!(colours(&graph, graph.ancestors(d)), vec!["a", "b", "c"]);
+        assert_eq!(colours(&graph, graph.descendants(a)), vec!["b", "c", "d"]);
+        assert!(graph.descendants(d).is_empty());
+
+        // 2 b, each with 3 + 1 d, and 1 c with 4 d.
+        assert_eq!(graph.total_contents(a), Ok(2 + 2 * 4 + 1 + 4));
+
+        let order = colours(&graph, &graph.topological_order().unwrap());
+        assert_eq!(order.first(), Some(&"a"));
+        assert_eq!(order.last(), Some(&"d"));
+    }
+
+    #[test]
+    fn test_queries_are_memoised() {
+        let mut graph = graph(&[("a", "b", 1), ("b", "c", 1)]);
+        let c = graph.id("c").unwrap();
+
+        let first = graph.ancestors(c).as_ptr();
+        assert_eq!(graph.ancestors(c).as_ptr(), first);
+        assert_eq!(graph.clone(), graph);
+
+        // Changing the graph throws away what was memoised.
+        let d = graph.intern("d");
+        graph.add_edge(d, c, 1);
+        assert_eq!(colours(&graph, graph.ancestors(c)), vec!["a", "b", "d"]);
+        assert_eq!(colours(&graph, graph.descendants(d)), vec!["c"]);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let graph = graph(&[("a", "b", 1), ("b", "c", 1), ("c", "b", 2), ("d", "e", 1)]);
+        let cycle = BagGraphError::Cycle(vec!["b".to_string(), "c".to_string(), "b".to_string()]);
+
+        assert_eq!(graph.topological_order(), Err(cycle.clone()));
+        assert_eq!(graph.total_contents(graph.id("a").unwrap()), Err(cycle));
+        assert_eq!(graph.total_contents(graph.id("d").unwrap()), Ok(1));
+        assert_eq!(colours(&graph, graph.ancestors(graph.id("b").unwrap())), vec!["a", "b", "c"]);
+
+        let error = graph.topological_order().unwrap_err();
+        assert_eq!(error.to_string(), "bags end up inside themselves: b -> c -> b");
+    }
+
+    #[test]
+    fn test_deep_and_wide() {
+        let mut graph = BagGraph::new();
+        let ids: Vec<usize> = (0..100_000).map(|i| graph.intern(&format!("bag {}", i))).collect();
+        for pair in ids.windows(2) {
+            graph.add_edge(pair[0], pair[1], 1);
+        }
+
+        assert_eq!(graph.total_contents(ids[0]), Ok(99_999));
+        assert_eq!(graph.ancestors(ids[99_999]).len(), 99_999);
+
+        let overflow = self::graph(&[("a", "b", u64::MAX), ("b", "c", 2)]);
+        assert_eq!(overflow.total_contents(0), Err(BagGraphError::Overflow("a".to_string())));
+    }
+}
@@ -0,0 +1,314 @@
+use std::str::FromStr;
+use std::collections::HashSet;
+
+use crate::error::{parse_lines, LineError};
+use crate::{ParseError, SolveError, Solution};
+
+fn parse_input(input: &str) -> Result<Computer, ParseError> {
+
+    let operations = parse_lines(input, Operation::from_str)?;
+
+    Ok(Computer::new(operations))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Operation {
+    Nop(i32),
+    Acc(i32),
+    Jmp(i32),
+}
+
+impl FromStr for Operation {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        let op_and_val: Vec<&str> = s.split(' ').collect();
+
+        if op_and_val.len() != 2 {
+            return Err(LineError::new(0, "failed to parse Operation because it did not have exactly one operation and one value part separated by a space"))
+        }
+
+        let op_str = op_and_val[0];
+        let val = op_and_val[1];
+
+        let value = if let Some(digits) = val.strip_prefix('+') {
+            parse_operation_value(s, digits)?
+        }
+        else if let Some(digits) = val.strip_prefix('-') {
+            -parse_operation_value(s, digits)?
+        }
+        else {
+            return Err(LineError::at(s, val, "failed to parse Operation because the value sign was not - or +"))
+        };
+
+        match op_str {
+            "nop" => Ok(Operation::Nop(value)),
+            "acc" => Ok(Operation::Acc(value)),
+            "jmp" => Ok(Operation::Jmp(value)),
+            unknown => Err(LineError::new(0, format!("failed to parse Operation because operation type '{}' was unknown", unknown))),
+        }
+    }
+}
+
+fn parse_operation_value(s: &str, digits: &str) -> Result<i32, LineError> {
+    digits.parse::<i32>()
+        .map_err(|e| LineError::at(s, digits, format!("failed to parse Operation value '{}': {}", digits, e)))
+}
+
+impl Operation {
+    // Each instruction runs at most once before we stop, so an i64
+    // accumulator can't overflow. A jump past the end of an i32 is as out
+    // of bounds as any other, so it saturates rather than wrapping back in.
+    fn execute(&self, state: &mut ComputerState) {
+
+        state.visited.insert(state.instruction_ptr);
+
+        match self {
+            Operation::Nop(_) => state.instruction_ptr += 1,
+            Operation::Acc(a) => {
+                state.accumulator += i64::from(*a);
+                state.instruction_ptr += 1;
+            }
+            Operation::Jmp(j) => state.instruction_ptr = state.instruction_ptr.saturating_add(*j),
+        }
+    }
+
+    // Acc instructions can't be flipped, so they're left as they are.
+    fn flipped(self) -> Self {
+        match self {
+            Operation::Nop(v) => Operation::Jmp(v),
+            Operation::Jmp(v) => Operation::Nop(v),
+            acc => acc,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComputerState {
+    instruction_ptr: i32,
+    visited: HashSet<i32>,
+    accumulator: i64,
+}
+
+impl Default for ComputerState {
+    fn default() -> Self { ComputerState::new() }
+}
+
+impl ComputerState {
+    pub fn new() -> Self {
+        ComputerState {
+            instruction_ptr: 0,
+            visited: HashSet::new(),
+            accumulator: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Computer {
+    state: ComputerState,
+    instructions: Vec<Operation>,
+}
+
+#[derive(Debug)]
+pub enum ExitStatus {
+    Success,
+    InfiniteLoop,
+    OutOfBounds,
+}
+
+impl Computer {
+    pub fn new(instructions: Vec<Operation>) -> Self {
+        Computer {
+            state: ComputerState::new(),
+            instructions,
+        }
+    }
+
+    pub fn run(&mut self) -> ExitStatus {
+        loop {
+            match self.check_finished() {
+                None => self.step(),
+                Some(status) => return status,
+            }
+        }
+    }
+
+    // If an instruction set causes an infinite loop, it should be
+    // fixable by flipping one of the nop/jmp instructions (or so
+    // I've been told). If it isn't, this gives back how the program
+    // still ends without any flips.
+    pub fn fix_instructions(&mut self) -> Result<(), ExitStatus> {
+        loop {
+            if let Some(status) = self.check_finished() {
+                return Err(status)
+            }
+
+            match self.current_operation() {
+                Operation::Nop(_) |
+                Operation::Jmp(_) => {
+                    match self.test_flip() {
+                        ExitStatus::Success => return Ok(()),
+                        _ => self.step(),
+                    }
+                },
+                _ => self.step(),
+            }
+        }
+    }
+
+    fn check_finished(&self) -> Option<ExitStatus> {
+
+        let current_ptr = self.state.instruction_ptr;
+
+        if self.state.visited.contains(&current_ptr) {
+            return Some(ExitStatus::InfiniteLoop);
+        }
+
+        let len = self.instructions.len() as i32;
+        if current_ptr > len || current_ptr < 0 {
+            return Some(ExitStatus::OutOfBounds);
+        }
+        if current_ptr == len {
+            return Some(ExitStatus::Success);
+        }
+
+        None
+    }
+
+    fn current_operation(&self) -> Operation {
+        self.instructions[self.state.instruction_ptr as usize]
+    }
+
+    fn current_operation_mut(&mut self) -> &mut Operation {
+        &mut self.instructions[self.state.instruction_ptr as usize]
+    }
+
+    fn step(&mut self) {
+        self.current_operation().execute(&mut self.state);
+    }
+
+    fn flip_branch(&mut self) {
+        let op = self.current_operation_mut();
+        *op = op.flipped();
+    }
+
+    fn test_flip(&mut self) -> ExitStatus {
+        let snapshot = self.state.clone();
+        self.flip_branch();
+
+        match self.run() {
+            ExitStatus::Success => ExitStatus::Success,
+            status => {
+                self.state = snapshot;
+                self.flip_branch();
+                status
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DayEight;
+
+impl Solution for DayEight {
+    const DAY: u32 = 8;
+
+    type Input = Computer;
+    type Output = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part_one(&mut input.clone())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part_two(&mut input.clone())
+    }
+}
+
+fn part_one(cmp: &mut Computer) -> Result<i64, SolveError> {
+    match cmp.run() {
+        ExitStatus::InfiniteLoop => Ok(cmp.state.accumulator),
+        ExitStatus::Success => Err(SolveError::new("expected an infinite loop, but the program terminated")),
+        ExitStatus::OutOfBounds => Err(SolveError::new("expected an infinite loop, but the program jumped out of bounds")),
+    }
+}
+
+fn part_two(cmp: &mut Computer) -> Result<i64, SolveError> {
+
+    match cmp.fix_instructions() {
+        Ok(()) => Ok(cmp.state.accumulator),
+        Err(ExitStatus::Success) => Err(SolveError::new("the program already terminates without fixing")),
+        Err(_) => Err(SolveError::new("no single nop/jmp flip makes the program terminate")),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::parse_input;
+    use super::part_one;
+    use super::part_two;
+
+    #[test]
+    fn test_parse_error_location() {
+        let error = parse_input("nop +0\nacc +x1\njmp +4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+
+        let error = parse_input("nop +0\nacc 1").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+
+        let error = parse_input("nop +0\nacc +1\nmul +4").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 0));
+    }
+
+    #[test]
+    fn test_programs_that_cant_be_solved() {
+        let error = part_one(&mut parse_input("nop +0").unwrap()).unwrap_err();
+        assert_eq!(error.reason, "expected an infinite loop, but the program terminated");
+
+        let error = part_one(&mut parse_input("acc +1\njmp +2147483647").unwrap()).unwrap_err();
+        assert_eq!(error.reason, "expected an infinite loop, but the program jumped out of bounds");
+
+        let error = part_two(&mut parse_input("nop +0").unwrap()).unwrap_err();
+        assert_eq!(error.reason, "the program already terminates without fixing");
+
+        let error = part_two(&mut parse_input("acc +1\njmp -1\njmp -2").unwrap()).unwrap_err();
+        assert_eq!(error.reason, "no single nop/jmp flip makes the program terminate");
+    }
+
+    #[test]
+    fn test_day_eight_part_one() {
+let example_input = String::from("nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6");
+
+        assert_eq!(part_one(&mut parse_input(&example_input).unwrap()), Ok(5));
+    }
+
+    #[test]
+    fn test_day_eight_part_two() {
+        let example_input = String::from("nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6");
+
+        assert_eq!(part_two(&mut parse_input(&example_input).unwrap()), Ok(8));
+    }
+}
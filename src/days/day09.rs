@@ -0,0 +1,207 @@
+use std::str::FromStr;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use crate::error::{parse_lines, LineError};
+use crate::{ParseError, SolveError, Solution};
+
+fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+
+    parse_lines(input, |line| {
+        i64::from_str(line).map_err(|e| LineError::new(0, format!("failed to parse '{}' as a number: {}", line, e)))
+    })
+}
+
+fn contains_sum(total: i64, values: &HashSet<i64>) -> bool {
+
+    for part in values.iter() {
+
+        if total.checked_sub(*part).is_some_and(|other| values.contains(&other)) {
+            return true
+        }
+    }
+
+    false
+}
+
+struct Xmas {
+    preamble_size: usize,
+    data: Vec<i64>,
+}
+
+impl Xmas {
+    pub fn find_number_without_sum(&self) -> Result<i64, SolveError> {
+
+        if self.data.len() <= self.preamble_size {
+            return Err(SolveError::new(format!("there are only {} numbers, which doesn't go past the preamble of {}", self.data.len(), self.preamble_size)))
+        }
+
+        let (preamble, rest) = self.data.split_at(self.preamble_size);
+        let mut set: HashSet<i64> = preamble.iter().cloned().collect();
+
+        for (i, &next) in rest.iter().enumerate() {
+
+            if !contains_sum(next, &set) {
+                return Ok(next);
+            }
+
+            set.remove(&self.data[i]);
+            set.insert(next);
+        }
+
+        Err(SolveError::new(format!("every number is the sum of two of the {} before it", self.preamble_size)))
+    }
+
+    // The sum is kept as an i128 so that it can't overflow however many
+    // numbers are in the window.
+    pub fn find_contiguous_sum(&self, target: i64) -> Result<i64, SolveError> {
+
+        let target = i128::from(target);
+        let mut sum: i128 = 0;
+        let mut queue: VecDeque<i64> = VecDeque::new();
+
+        for &number in self.data.iter() {
+
+            queue.push_back(number);
+            sum += i128::from(number);
+
+            // The set needs at least two numbers, so the target on its own
+            // doesn't count.
+            while let Some(&first) = queue.front() {
+                if sum < target {
+                    break
+                }
+
+                if sum == target && queue.len() > 1 {
+                    // I can save a bit of time by keeping track of the min and max
+                    // as I go, but I don't think it's worth it
+                    let (min, max) = (queue.iter().min().unwrap(), queue.iter().max().unwrap());
+                    return min.checked_add(*max)
+                        .ok_or_else(|| SolveError::new(format!("the sum of {} and {} is too big", min, max)))
+                }
+                else {
+                    sum -= i128::from(first);
+                    queue.pop_front();
+                }
+            }
+        }
+
+        Err(SolveError::new(format!("no contiguous set of at least two numbers adds up to {}", target)))
+    }
+}
+
+pub struct DayNine {
+    pub preamble_size: usize,
+}
+
+impl Default for DayNine {
+    fn default() -> Self { DayNine { preamble_size: 25 } }
+}
+
+impl Solution for DayNine {
+    const DAY: u32 = 9;
+
+    type Input = Vec<i64>;
+    type Output = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part_one(&Xmas { preamble_size: self.preamble_size, data: input.to_vec() })
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part_two(&Xmas { preamble_size: self.preamble_size, data: input.to_vec() })
+    }
+}
+
+// TODO refactor into a struct (XMAS)
+fn part_one(xmas: &Xmas) -> Result<i64, SolveError> {
+
+    xmas.find_number_without_sum()
+}
+
+// again, refactor this at some point
+fn part_two(xmas: &Xmas) -> Result<i64, SolveError> {
+
+    let target = xmas.find_number_without_sum()?;
+    xmas.find_contiguous_sum(target)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::parse_input;
+    use super::Xmas;
+    use super::part_one;
+    use super::part_two;
+
+    #[test]
+    fn test_day_nine_part_one() {
+let example_input = String::from("35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576");
+
+        let xmas = &Xmas { preamble_size: 5, data: parse_input(&example_input).unwrap() };
+        assert_eq!(part_one(xmas), Ok(127));
+    }
+
+    #[test]
+    fn test_day_nine_part_two() {
+        let example_input = String::from("35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576");
+
+        let xmas = &Xmas { preamble_size: 5, data: parse_input(&example_input).unwrap() };
+        assert_eq!(part_two(xmas), Ok(62));
+    }
+
+    #[test]
+    fn test_inputs_without_a_solution() {
+        let xmas = &Xmas { preamble_size: 25, data: parse_input("1\n2").unwrap() };
+        assert_eq!(part_one(xmas).unwrap_err().reason, "there are only 2 numbers, which doesn't go past the preamble of 25");
+
+        let xmas = &Xmas { preamble_size: 2, data: parse_input("1\n2\n3\n5\n8").unwrap() };
+        assert_eq!(part_one(xmas).unwrap_err().reason, "every number is the sum of two of the 2 before it");
+
+        let xmas = &Xmas { preamble_size: 2, data: parse_input("1\n2\n9").unwrap() };
+        assert_eq!(xmas.find_contiguous_sum(9).unwrap_err().reason, "no contiguous set of at least two numbers adds up to 9");
+        assert_eq!(xmas.find_contiguous_sum(-4).unwrap_err().reason, "no contiguous set of at least two numbers adds up to -4");
+    }
+}
@@ -0,0 +1,82 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day12;
+
+use crate::bench::{self, DayBench};
+use crate::runner::{self, PuzzleInput, Solved};
+use crate::cli::Options;
+use crate::{DayError, Solution};
+
+// Every day that has a solution, in order.
+pub const IMPLEMENTED: [u32; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 12];
+
+// Something to do with a day's Solution that doesn't care which day it is.
+pub trait Visitor {
+    type Output;
+
+    fn visit<S: Solution>(self) -> Self::Output;
+}
+
+// Hand the given day's Solution type to the visitor, or None if that day
+// hasn't been done yet.
+pub fn visit<V: Visitor>(day: u32, visitor: V) -> Option<V::Output> {
+    match day {
+        1 => Some(visitor.visit::<day01::DayOne>()),
+        2 => Some(visitor.visit::<day02::DayTwo>()),
+        3 => Some(visitor.visit::<day03::DayThree>()),
+        4 => Some(visitor.visit::<day04::DayFour>()),
+        5 => Some(visitor.visit::<day05::DayFive>()),
+        6 => Some(visitor.visit::<day06::DaySix>()),
+        7 => Some(visitor.visit::<day07::DaySeven>()),
+        8 => Some(visitor.visit::<day08::DayEight>()),
+        9 => Some(visitor.visit::<day09::DayNine>()),
+        12 => Some(visitor.visit::<day12::DayTwelve>()),
+        _ => None,
+    }
+}
+
+struct Solve<'a> {
+    input: &'a PuzzleInput,
+    options: &'a Options,
+}
+
+impl Visitor for Solve<'_> {
+    type Output = Solved;
+
+    fn visit<S: Solution>(self) -> Self::Output {
+        match runner::configure::<S>(self.options) {
+            Ok(solution) => runner::solve_and_report(&solution, self.input),
+            Err(e) => (Err(e), Ok(None)),
+        }
+    }
+}
+
+pub fn solve(day: u32, input: &PuzzleInput, options: &Options) -> Option<Solved> {
+    visit(day, Solve { input, options })
+}
+
+struct Bench<'a> {
+    input: &'a str,
+    options: &'a Options,
+    runs: usize,
+}
+
+impl Visitor for Bench<'_> {
+    type Output = Result<DayBench, DayError>;
+
+    fn visit<S: Solution>(self) -> Self::Output {
+        let solution = runner::configure::<S>(self.options)?;
+        bench::bench(&solution, self.input, self.options.warmup_runs, self.runs)
+    }
+}
+
+pub fn bench(day: u32, input: &str, options: &Options, runs: usize) -> Option<Result<DayBench, DayError>> {
+    visit(day, Bench { input, options, runs })
+}
@@ -0,0 +1,175 @@
+use std::error::Error;
+use std::fmt;
+
+// Something wrong with a single line of input, found by code that only
+// ever sees that line. The column is a byte offset into the line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError {
+    pub column: usize,
+    pub reason: String,
+}
+
+impl LineError {
+    pub fn new(column: usize, reason: impl Into<String>) -> Self {
+        LineError {
+            column,
+            reason: reason.into(),
+        }
+    }
+
+    // An error about part of a line, where part is a slice of that line.
+    pub fn at(line: &str, part: &str, reason: impl Into<String>) -> Self {
+        let column = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+        LineError::new(column.min(line.len()), reason)
+    }
+}
+
+// A LineError once we know where in the input it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    // Line numbers are 1-based, as they would be in an editor.
+    pub fn new(line: usize, text: &str, error: LineError) -> Self {
+        ParseError {
+            line,
+            column: error.column,
+            text: text.to_string(),
+            reason: error.reason,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+
+        // Point at the offending character, counting chars rather than
+        // bytes so the caret still lines up on non-ASCII input.
+        let offset = self.text.get(..self.column).map_or(0, |s| s.chars().count());
+
+        writeln!(f, "line {}, column {}: {}", self.line, offset + 1, self.reason)?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {}^", " ".repeat(offset))
+    }
+}
+
+impl Error for ParseError {}
+
+// A puzzle that parsed fine but has no answer, e.g. because no entries add
+// up to the target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveError {
+    pub reason: String,
+}
+
+impl SolveError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        SolveError {
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl Error for SolveError {}
+
+// Anything that can stop a day from giving its answers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DayError {
+    Parse(ParseError),
+    Solve { part: u32, error: SolveError },
+    BadOption(String),
+    Report(SolveError),
+}
+
+impl fmt::Display for DayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DayError::Parse(e) => write!(f, "failed to parse input\n{}", e),
+            DayError::Solve { part, error } => write!(f, "failed to solve part {}: {}", part, error),
+            DayError::BadOption(message) => write!(f, "{}", message),
+            DayError::Report(error) => write!(f, "failed to report: {}", error),
+        }
+    }
+}
+
+impl Error for DayError {}
+
+impl From<ParseError> for DayError {
+    fn from(error: ParseError) -> Self {
+        DayError::Parse(error)
+    }
+}
+
+// Parse every non-blank line of the input with the given function, giving
+// back the first error with its line number filled in.
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, LineError>,
+{
+    parse_numbered_lines(input, parse_line)
+        .map(|parsed| parsed.into_iter().map(|(_, value)| value).collect())
+}
+
+// The same as parse_lines, but keeping the (1-based) line number that each
+// value came from.
+pub fn parse_numbered_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<(usize, T)>, ParseError>
+where
+    F: Fn(&str) -> Result<T, LineError>,
+{
+    input.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            parse_line(line)
+                .map(|value| (i + 1, value))
+                .map_err(|e| ParseError::new(i + 1, line, e))
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_lines, parse_numbered_lines};
+    use super::LineError;
+
+    fn parse_number(line: &str) -> Result<u32, LineError> {
+        line.parse::<u32>().map_err(|e| LineError::new(0, e.to_string()))
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(parse_lines("1\n2\n\n3\n", parse_number), Ok(vec![1, 2, 3]));
+
+        let error = parse_lines("1\n2\nthree", parse_number).unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.text, "three");
+
+        assert_eq!(parse_numbered_lines("1\n\n3\n", parse_number), Ok(vec![(1, 1), (3, 3)]));
+    }
+
+    #[test]
+    fn test_display_points_at_column() {
+        let error = parse_lines("1-3 a: abc\n1-x b: cde", |line| {
+            if line.contains('x') {
+                Err(LineError::new(line.find('x').unwrap(), "bad maximum"))
+            }
+            else {
+                Ok(())
+            }
+        }).unwrap_err();
+
+        assert_eq!(error.to_string(), "line 2, column 3: bad maximum\n    1-x b: cde\n      ^");
+    }
+}
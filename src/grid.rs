@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{parse_lines, LineError};
+use crate::ParseError;
+
+// A position on a grid. Signed, so that stepping off the top or left edge
+// can be wrapped or looked up like stepping off any other edge.
+pub type Point = (i64, i64);
+
+// What happens past the edges of the grid:
+//
+// - Bounded: nothing is there.
+// - Cylinder: the grid repeats forever left and right, like the toboggan map.
+// - Torus: the grid repeats in both directions.
+// - Infinite: it goes on forever filled with the background, except where
+//   cells have been set, which are kept sparsely.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Topology<T> {
+    Bounded,
+    Cylinder,
+    Torus,
+    Infinite(T),
+}
+
+// Where a point ends up once the topology has been taken into account.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Location {
+    Inside(usize),
+    Outside(Point),
+}
+
+const NEIGHBOURS_4: [Point; 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+const NEIGHBOURS_8: [Point; 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    topology: Topology<T>,
+    outside: HashMap<Point, T>,
+}
+
+impl<T> Grid<T> {
+    // A bounded grid from its cells, row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+
+        assert_eq!(cells.len(), width * height, "a {}x{} grid needs {} cells", width, height, width * height);
+
+        Grid {
+            width,
+            height,
+            cells,
+            topology: Topology::Bounded,
+            outside: HashMap::new(),
+        }
+    }
+
+    // Read a grid with one character per cell, turning each into a cell
+    // with the given function. Every row has to be the same width.
+    pub fn parse<F>(input: &str, cell: F) -> Result<Self, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let width = input.lines().find(|row| !row.trim().is_empty()).map_or(0, |row| row.chars().count());
+
+        let rows = parse_lines(input, |row| {
+            let parsed = row.char_indices()
+                .map(|(i, c)| cell(c).ok_or_else(|| LineError::new(i, format!("unexpected character '{}' in grid", c))))
+                .collect::<Result<Vec<T>, LineError>>()?;
+
+            if parsed.len() != width {
+                return Err(LineError::new(0, format!("row is {} wide but the grid is {} wide", parsed.len(), width)))
+            }
+
+            Ok(parsed)
+        })?;
+
+        let height = rows.len();
+        Ok(Grid::new(width, height, rows.into_iter().flatten().collect()))
+    }
+
+    pub fn with_topology(mut self, topology: Topology<T>) -> Self {
+        self.topology = topology;
+        self.outside.clear();
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn topology(&self) -> &Topology<T> {
+        &self.topology
+    }
+
+    fn wrap(value: i64, size: usize) -> Option<usize> {
+        match size {
+            0 => None,
+            size => Some(value.rem_euclid(size as i64) as usize),
+        }
+    }
+
+    fn in_range(value: i64, size: usize) -> Option<usize> {
+        if value >= 0 && (value as usize) < size { Some(value as usize) } else { None }
+    }
+
+    fn locate(&self, (x, y): Point) -> Option<Location> {
+
+        let (column, row) = match self.topology {
+            Topology::Bounded | Topology::Infinite(_) => (Self::in_range(x, self.width), Self::in_range(y, self.height)),
+            Topology::Cylinder => (Self::wrap(x, self.width), Self::in_range(y, self.height)),
+            Topology::Torus => (Self::wrap(x, self.width), Self::wrap(y, self.height)),
+        };
+
+        match (column, row, &self.topology) {
+            (Some(column), Some(row), _) => Some(Location::Inside(row * self.width + column)),
+            (_, _, Topology::Infinite(_)) => Some(Location::Outside((x, y))),
+            _ => None,
+        }
+    }
+
+    // The point on the grid itself that a point stands for, once it's been
+    // wrapped round, or None if there's nothing there. Points outside an
+    // infinite grid are left as they are.
+    pub fn resolve(&self, point: Point) -> Option<Point> {
+        match self.locate(point)? {
+            Location::Inside(index) => Some(((index % self.width) as i64, (index / self.width) as i64)),
+            Location::Outside(point) => Some(point),
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        match (self.locate(point)?, &self.topology) {
+            (Location::Inside(index), _) => Some(&self.cells[index]),
+            (Location::Outside(point), Topology::Infinite(background)) => Some(self.outside.get(&point).unwrap_or(background)),
+            (Location::Outside(_), _) => None,
+        }
+    }
+
+    // Every point on the grid itself, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| (x as i64, y as i64)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    fn neighbours<'a>(&'a self, (x, y): Point, offsets: &'a [Point]) -> impl Iterator<Item = Point> + 'a {
+        offsets.iter().filter_map(move |&(dx, dy)| self.resolve((x + dx, y + dy)))
+    }
+
+    // The points up, left, right and down from this one that are on the
+    // grid, wrapped round as needed.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &NEIGHBOURS_4)
+    }
+
+    // The same, but including the diagonals.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &NEIGHBOURS_8)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Setting a cell off the edge of an infinite grid grows it there.
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        match (self.locate(point)?, &self.topology) {
+            (Location::Inside(index), _) => Some(&mut self.cells[index]),
+            (Location::Outside(point), Topology::Infinite(background)) => {
+                Some(self.outside.entry(point).or_insert_with(|| background.clone()))
+            }
+            (Location::Outside(_), _) => None,
+        }
+    }
+
+    // Returns whether there was anywhere to put the value.
+    pub fn set(&mut self, point: Point, value: T) -> bool {
+        match self.get_mut(point) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Plain (x, y) indexing only reaches the cells of the grid itself, without
+// any wrapping, and panics past the edges like a Vec would.
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({}, {}) is off a {}x{} grid", x, y, self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({}, {}) is off a {}x{} grid", x, y, self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, Topology};
+
+    fn example() -> Grid<bool> {
+        Grid::parse("#..\n.#.\n..#\n#..\n", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example();
+
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert!(grid[(1, 1)]);
+        assert!(!grid[(2, 1)]);
+        assert_eq!(grid.rows().filter(|row| row[0]).count(), 2);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cell = |c| if c == '.' { Some(()) } else { None };
+
+        let error = Grid::parse("...\n.x.", cell).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = Grid::parse("...\n..", cell).unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn test_topologies() {
+        let bounded = example();
+        assert_eq!(bounded.get((3, 0)), None);
+        assert_eq!(bounded.get((0, -1)), None);
+
+        let cylinder = example().with_topology(Topology::Cylinder);
+        assert_eq!(cylinder.get((3, 0)), Some(&true));
+        assert_eq!(cylinder.get((-2, 1)), Some(&true));
+        assert_eq!(cylinder.get((0, 4)), None);
+
+        let torus = example().with_topology(Topology::Torus);
+        assert_eq!(torus.get((3, 4)), Some(&true));
+        assert_eq!(torus.resolve((-1, -1)), Some((2, 3)));
+
+        let mut infinite = example().with_topology(Topology::Infinite(false));
+        assert_eq!(infinite.get((100, -100)), Some(&false));
+        assert!(infinite.set((100, -100), true));
+        assert_eq!(infinite.get((100, -100)), Some(&true));
+        assert_eq!(infinite.get((2, 2)), Some(&true));
+
+        assert!(!example().set((5, 5), true));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let bounded = example();
+        assert_eq!(bounded.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(bounded.neighbours8((1, 1)).count(), 8);
+        assert_eq!(bounded.neighbours8((0, 0)).count(), 3);
+
+        let cylinder = example().with_topology(Topology::Cylinder);
+        assert_eq!(cylinder.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(2, 0), (1, 0), (0, 1)]);
+
+        let torus = example().with_topology(Topology::Torus);
+        assert_eq!(torus.neighbours8((0, 0)).count(), 8);
+        assert!(torus.neighbours4((0, 0)).any(|p| p == (0, 3)));
+
+        let infinite = example().with_topology(Topology::Infinite(false));
+        assert!(infinite.neighbours4((0, 0)).any(|p| p == (0, -1)));
+    }
+}
@@ -0,0 +1,55 @@
+#[macro_use] extern crate lazy_static;
+
+use std::fmt::Display;
+use std::io::BufRead;
+
+pub mod bench;
+pub mod cli;
+pub mod days;
+pub mod error;
+pub mod grid;
+pub mod output;
+pub mod records;
+pub mod runner;
+pub mod sets;
+pub mod table;
+pub mod verify;
+
+pub use error::{DayError, ParseError, SolveError};
+pub use runner::run;
+
+// A single day's puzzle. Each day only has to say how to turn the raw
+// input into something useful, and how to solve both parts from that.
+// Solutions shouldn't print anything themselves, as the runner owns stdout
+// and may be writing JSON or CSV to it.
+pub trait Solution: Default {
+    const DAY: u32;
+
+    type Input;
+    type Output: Display;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Output, SolveError>;
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Output, SolveError>;
+
+    // Days can take their own options on the command line, given as
+    // `--name value`, to solve variations on the puzzle.
+    fn set_option(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(format!("day {} has no option --{}", Self::DAY, name))
+    }
+    // Anything a day has to say about its input beyond the answers, like
+    // a breakdown asked for with one of its options, or an image to save.
+    // This is only run once both parts are solved, never while
+    // benchmarking, and whatever it gives back goes to stderr so it stays
+    // out of the answers.
+    fn report(&self, _input: &Self::Input) -> Result<Option<String>, SolveError> {
+        Ok(None)
+    }
+
+    // Days that can work out both parts a piece at a time can solve
+    // straight from a reader, so that inputs too big to hold in memory can
+    // still be solved. The runner uses this with --stream.
+    fn solve_reader(&self, _reader: &mut dyn BufRead) -> Result<(Self::Output, Self::Output), DayError> {
+        Err(DayError::BadOption(format!("day {} can't solve with --stream", Self::DAY)))
+    }
+}
@@ -0,0 +1,152 @@
+use std::str::FromStr;
+
+use crate::runner::DayResult;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            unknown => Err(format!("unknown format '{}', expected text, json or csv", unknown)),
+        }
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    }
+    else {
+        s.to_string()
+    }
+}
+
+// Each result gives one record per part. Setup is shared by both parts of a
+// day, so it's repeated on each of them.
+struct Record<'a> {
+    day: u32,
+    source: &'a str,
+    part: u32,
+    answer: &'a str,
+    setup_ns: u128,
+    part_ns: u128,
+}
+
+fn records(results: &[DayResult]) -> Vec<Record<'_>> {
+    results.iter()
+        .flat_map(|r| vec![
+            Record {
+                day: r.day,
+                source: &r.source,
+                part: 1,
+                answer: &r.part_one,
+                setup_ns: r.timings.setup.as_nanos(),
+                part_ns: r.timings.part_one.as_nanos(),
+            },
+            Record {
+                day: r.day,
+                source: &r.source,
+                part: 2,
+                answer: &r.part_two,
+                setup_ns: r.timings.setup.as_nanos(),
+                part_ns: r.timings.part_two.as_nanos(),
+            },
+        ])
+        .collect()
+}
+
+pub fn to_json(results: &[DayResult]) -> String {
+
+    let entries: Vec<String> = records(results).iter()
+        .map(|r| format!(
+            "  {{\"day\": {}, \"input\": {}, \"part\": {}, \"answer\": {}, \"setup_ns\": {}, \"part_ns\": {}}}",
+            r.day, json_string(r.source), r.part, json_string(r.answer), r.setup_ns, r.part_ns,
+        ))
+        .collect();
+
+    format!("[\n{}\n]", entries.join(",\n"))
+}
+
+pub fn to_csv(results: &[DayResult]) -> String {
+
+    let mut lines = vec!["day,input,part,answer,setup_ns,part_ns".to_string()];
+    lines.extend(records(results).iter()
+        .map(|r| format!("{},{},{},{},{},{}", r.day, csv_field(r.source), r.part, csv_field(r.answer), r.setup_ns, r.part_ns)));
+
+    lines.join("\n")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::runner::{DayResult, Timings};
+    use super::{csv_field, json_string, to_csv, to_json};
+
+    fn example_results() -> Vec<DayResult> {
+        vec![DayResult {
+            day: 3,
+            source: "aoc03/input.txt".to_string(),
+            part_one: "162".to_string(),
+            part_two: "3064612320".to_string(),
+            timings: Timings {
+                setup: Duration::from_nanos(100),
+                part_one: Duration::from_nanos(20),
+                part_two: Duration::from_nanos(30),
+            },
+        }]
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(to_json(&example_results()), r#"[
+  {"day": 3, "input": "aoc03/input.txt", "part": 1, "answer": "162", "setup_ns": 100, "part_ns": 20},
+  {"day": 3, "input": "aoc03/input.txt", "part": 2, "answer": "3064612320", "setup_ns": 100, "part_ns": 30}
+]"#);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(to_csv(&example_results()), "day,input,part,answer,setup_ns,part_ns
+3,aoc03/input.txt,1,162,100,20
+3,aoc03/input.txt,2,3064612320,100,30");
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(json_string("a \"b\"\n"), r#""a \"b\"\n""#);
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,\"b\""), r#""a,""b""""#);
+    }
+}
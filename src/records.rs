@@ -0,0 +1,127 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+use crate::error::LineError;
+use crate::ParseError;
+
+// A run of lines with something on them, as used for puzzles whose input is
+// made of records separated by blank lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub first_line: usize,
+    pub lines: Vec<String>,
+}
+
+impl Record {
+    // The (1-based) lines of the input the record came from.
+    pub fn line_range(&self) -> RangeInclusive<usize> {
+        self.first_line..=self.first_line + self.lines.len() - 1
+    }
+
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.lines.iter().enumerate().map(move |(i, line)| (self.first_line + i, line.as_str()))
+    }
+}
+
+// Reads records one at a time, so the whole input never has to be in
+// memory. Any line that's empty or only whitespace ends a record, however
+// many of them there are, and lines can end in either \n or \r\n.
+pub struct Records<R> {
+    reader: R,
+    line: usize,
+    buffer: String,
+    failed: bool,
+}
+
+impl<R: BufRead> Records<R> {
+    pub fn new(reader: R) -> Self {
+        Records { reader, line: 0, buffer: String::new(), failed: false }
+    }
+
+    fn next_line(&mut self) -> Result<Option<String>, ParseError> {
+
+        self.buffer.clear();
+        let read = self.reader.read_line(&mut self.buffer)
+            .map_err(|e| ParseError::new(self.line + 1, "", LineError::new(0, format!("failed to read line: {}", e))))?;
+
+        if read == 0 {
+            return Ok(None)
+        }
+
+        self.line += 1;
+        let line = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+        Ok(Some(line.strip_suffix('\r').unwrap_or(line).to_string()))
+    }
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<Record, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+
+        if self.failed {
+            return None
+        }
+
+        let mut record: Option<Record> = None;
+
+        loop {
+            let line = match self.next_line() {
+                Ok(line) => line,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e))
+                }
+            };
+
+            match (line, &mut record) {
+                (None, _) => return record.map(Ok),
+                (Some(line), _) if line.trim().is_empty() => {
+                    if record.is_some() {
+                        return record.map(Ok)
+                    }
+                }
+                (Some(line), Some(record)) => record.lines.push(line),
+                (Some(line), None) => record = Some(Record { first_line: self.line, lines: vec![line] }),
+            }
+        }
+    }
+}
+
+// The records of input that's already in memory.
+pub fn split_records(input: &str) -> impl Iterator<Item = Record> + '_ {
+    Records::new(input.as_bytes()).map(|record| record.expect("reading from a string can't fail"))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::{split_records, Records};
+
+    #[test]
+    fn test_split_records() {
+        let records: Vec<_> = split_records("\n a b\nc\r\n \t\r\n\n\nd\r\n  \ne\nf").collect();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].lines, vec![" a b", "c"]);
+        assert_eq!(records[0].line_range(), 2..=3);
+        assert_eq!(records[1].lines, vec!["d"]);
+        assert_eq!(records[1].line_range(), 7..=7);
+        assert_eq!(records[2].numbered_lines().collect::<Vec<_>>(), vec![(9, "e"), (10, "f")]);
+
+        assert_eq!(split_records("").count(), 0);
+        assert_eq!(split_records("\n  \n").count(), 0);
+    }
+
+    #[test]
+    fn test_read_error() {
+        let input = BufReader::new(&b"a\n\nb\xff\n"[..]);
+        let results: Vec<_> = Records::new(input).collect();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().lines, vec!["a"]);
+        assert_eq!(results[1].as_ref().unwrap_err().line, 3);
+    }
+}
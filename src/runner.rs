@@ -0,0 +1,412 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::bench;
+use crate::cli::{self, Options};
+use crate::output::{self, Format};
+use crate::{DayError, Solution};
+
+#[derive(Debug, Copy, Clone)]
+pub struct Timings {
+    pub setup: Duration,
+    pub part_one: Duration,
+    pub part_two: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.setup + self.part_one + self.part_two
+    }
+}
+
+// A puzzle input along with where it came from, which is either a path or
+// '-' for stdin.
+#[derive(Debug, Clone)]
+pub struct PuzzleInput {
+    pub source: String,
+    pub text: String,
+}
+
+impl PuzzleInput {
+    pub fn read(source: &str) -> Result<Self, String> {
+
+        if source != "-" {
+            return PuzzleInput::read_path(Path::new(source))
+        }
+
+        PuzzleInput::read_stdin(io::stdin())
+    }
+
+    // Anything standing in for stdin, so that it can be given in tests.
+    pub fn read_stdin<R: Read>(mut stdin: R) -> Result<Self, String> {
+
+        let mut text = String::new();
+        stdin.read_to_string(&mut text)
+            .map_err(|e| format!("could not read stdin: {}", e))?;
+
+        Ok(PuzzleInput { source: "-".to_string(), text })
+    }
+
+    pub fn read_path(path: &Path) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map(|text| PuzzleInput { source: path.display().to_string(), text })
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))
+    }
+}
+
+// The answers for a single day, already formatted, along with how long
+// each stage took to produce them.
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub day: u32,
+    pub source: String,
+    pub part_one: String,
+    pub part_two: String,
+    pub timings: Timings,
+}
+
+// A day's results along with its report, if it gave one. They're kept
+// apart since the report can still be wanted when solving fails.
+pub type Solved = (Result<DayResult, DayError>, Result<Option<String>, DayError>);
+
+// A day's Solution with any day options from the command line applied.
+pub fn configure<S: Solution>(options: &Options) -> Result<S, DayError> {
+
+    let mut solution = S::default();
+    for (name, value) in &options.day_options {
+        solution.set_option(name, value).map_err(DayError::BadOption)?;
+    }
+
+    Ok(solution)
+}
+
+pub fn solve<S: Solution>(solution: &S, input: &PuzzleInput) -> Result<DayResult, DayError> {
+    let start_time = Instant::now();
+    let parsed = solution.parse(&input.text)?;
+
+    solve_parsed(solution, input, &parsed, start_time)
+}
+
+// Solve an input and then ask the day for its report on it, which is left
+// out of the timings. The report is still asked for when solving fails,
+// as that's often when it's wanted most, but not if the input won't parse.
+pub fn solve_and_report<S: Solution>(solution: &S, input: &PuzzleInput) -> Solved {
+    let start_time = Instant::now();
+    let parsed = match solution.parse(&input.text) {
+        Ok(parsed) => parsed,
+        Err(e) => return (Err(e.into()), Ok(None)),
+    };
+
+    let result = solve_parsed(solution, input, &parsed, start_time);
+    let report = solution.report(&parsed).map_err(DayError::Report);
+
+    (result, report)
+}
+
+fn solve_parsed<S: Solution>(solution: &S, input: &PuzzleInput, parsed: &S::Input, start_time: Instant) -> Result<DayResult, DayError> {
+    let setup_time = Instant::now();
+    let p1 = solution.part_one(parsed).map_err(|error| DayError::Solve { part: 1, error })?;
+    let part_1_time = Instant::now();
+    let p2 = solution.part_two(parsed).map_err(|error| DayError::Solve { part: 2, error })?;
+    let part_2_time = Instant::now();
+
+    Ok(DayResult {
+        day: S::DAY,
+        source: input.source.clone(),
+        part_one: p1.to_string(),
+        part_two: p2.to_string(),
+        timings: Timings {
+            setup: setup_time - start_time,
+            part_one: part_1_time - setup_time,
+            part_two: part_2_time - part_1_time,
+        },
+    })
+}
+
+// The inputs are kept next to each day's binary, so find them relative to
+// the crate rather than wherever we happen to be run from.
+pub fn default_inputs_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("bin")
+}
+
+pub fn input_path(inputs_dir: &Path, day: u32) -> PathBuf {
+    inputs_dir.join(format!("aoc{:02}", day)).join("input.txt")
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1);
+}
+
+fn check_stdin_read_once(sources: &[String]) -> Result<(), String> {
+
+    if sources.iter().filter(|&source| source == "-").count() > 1 {
+        return Err("stdin can only be read once".to_string())
+    }
+
+    Ok(())
+}
+
+// With no paths given we fall back on the day's usual input. Each input is
+// read separately, so one that can't be read is reported alongside the
+// results of the rest, the same as one that fails to solve.
+fn get_inputs<R: Read>(day: u32, options: &Options, sources: &[String], stdin: R) -> Result<Vec<Result<PuzzleInput, String>>, String> {
+
+    check_stdin_read_once(sources)?;
+
+    let mut stdin = Some(stdin);
+    let inputs = match sources {
+        [] => vec![PuzzleInput::read_path(&input_path(&options.inputs_dir, day))],
+        _ => sources.iter()
+            .map(|source| match source.as_str() {
+                "-" => PuzzleInput::read_stdin(stdin.take().unwrap()),
+                path => PuzzleInput::read_path(Path::new(path)),
+            })
+            .collect(),
+    };
+
+    Ok(inputs)
+}
+
+// Solve straight from a reader, for days that can. Both parts come out of
+// the same pass over the input, so all of the time counts as setup.
+pub fn solve_reader<S: Solution>(solution: &S, source: &str, reader: &mut dyn BufRead) -> Result<DayResult, DayError> {
+    let start_time = Instant::now();
+    let (p1, p2) = solution.solve_reader(reader)?;
+
+    Ok(DayResult {
+        day: S::DAY,
+        source: source.to_string(),
+        part_one: p1.to_string(),
+        part_two: p2.to_string(),
+        timings: Timings {
+            setup: start_time.elapsed(),
+            part_one: Duration::ZERO,
+            part_two: Duration::ZERO,
+        },
+    })
+}
+
+fn open_reader(source: &str) -> Result<Box<dyn BufRead>, String> {
+
+    if source == "-" {
+        return Ok(Box::new(io::stdin().lock()))
+    }
+
+    fs::File::open(source)
+        .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+        .map_err(|e| format!("could not read {}: {}", source, e))
+}
+
+// With --stream each input is solved as it's read rather than read in
+// full first. There are no reports, since those need the whole input, and
+// parse_options won't take the options that ask for them.
+fn stream_inputs<S: Solution>(solution: &S, options: &Options, sources: &[String]) -> Result<(Vec<DayResult>, bool), String> {
+
+    check_stdin_read_once(sources)?;
+
+    let sources = match sources {
+        [] => vec![input_path(&options.inputs_dir, S::DAY).display().to_string()],
+        _ => sources.to_vec(),
+    };
+
+    let mut results = Vec::new();
+    let mut failed = false;
+    for source in &sources {
+        let result = open_reader(source).and_then(|mut reader| {
+            solve_reader(solution, source, &mut reader).map_err(|e| day_failed(S::DAY, source, e))
+        });
+
+        match result {
+            Ok(result) => results.push(result),
+            Err(message) => {
+                eprintln!("error: {}", message);
+                failed = true;
+            }
+        }
+    }
+
+    Ok((results, failed))
+}
+
+pub fn day_failed(day: u32, source: &str, error: DayError) -> String {
+    format!("day {} from {}: {}", day, source, error)
+}
+
+// Solve every input given, or benchmark it if asked to, and print the
+// results in whichever format was asked for.
+pub fn run<S: Solution>() {
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (options, sources) = cli::parse_options(&args).unwrap_or_else(|e| {
+        eprintln!("{}\n\nUsage: aoc{:02} [INPUT|-]... [OPTIONS]\n\n{}", e, S::DAY, cli::OPTIONS_USAGE);
+        process::exit(2);
+    });
+
+    let solution = configure::<S>(&options)
+        .unwrap_or_else(|e| exit_with_error(&e.to_string()));
+
+    if options.stream {
+        let (results, failed) = stream_inputs(&solution, &options, &sources)
+            .unwrap_or_else(|e| exit_with_error(&e));
+
+        print_results(options.format, &results, sources.len().max(1));
+        if failed {
+            process::exit(1);
+        }
+        return
+    }
+
+    let inputs = get_inputs(S::DAY, &options, &sources, io::stdin())
+        .unwrap_or_else(|e| exit_with_error(&e));
+
+    if let Some(runs) = options.bench_runs {
+        let input = match inputs.as_slice() {
+            [Ok(input)] => input,
+            [Err(e)] => exit_with_error(e),
+            _ => exit_with_error("--bench only takes a single input"),
+        };
+
+        let result = bench::bench(&solution, &input.text, options.warmup_runs, runs)
+            .unwrap_or_else(|e| exit_with_error(&day_failed(S::DAY, &input.source, e)));
+
+        match bench::report(&[result], &options) {
+            Ok(false) => return,
+            Ok(true) => process::exit(1),
+            Err(message) => exit_with_error(&message),
+        }
+    }
+
+    // Carry on past inputs that fail, so one bad file doesn't hide the
+    // results for the rest. Reports go to stderr as each input is done,
+    // which keeps stdout to the answers whatever format they're in.
+    let mut results = Vec::new();
+    let mut failed = false;
+    for input in &inputs {
+        let input = match input {
+            Ok(input) => input,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                failed = true;
+                continue
+            }
+        };
+
+        let (result, report) = solve_and_report(&solution, input);
+
+        match report {
+            Ok(Some(report)) => {
+                if inputs.len() > 1 {
+                    eprintln!("== {} ==", input.source);
+                }
+                eprintln!("{}", report);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("error: {}", day_failed(S::DAY, &input.source, e));
+                failed = true;
+            }
+        }
+
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                eprintln!("error: {}", day_failed(S::DAY, &input.source, e));
+                failed = true;
+            }
+        }
+    }
+
+    print_results(options.format, &results, inputs.len());
+    if failed {
+        process::exit(1);
+    }
+}
+
+// Results are only headed with where they came from when there's more than
+// one input.
+fn print_results(format: Format, results: &[DayResult], input_count: usize) {
+    match format {
+        Format::Json => println!("{}", output::to_json(results)),
+        Format::Csv => println!("{}", output::to_csv(results)),
+        Format::Text if results.len() == 1 && input_count == 1 => print_result(&results[0]),
+        Format::Text => {
+            for result in results {
+                println!("== {} ==", result.source);
+                print_result(result);
+                println!();
+            }
+        }
+    }
+}
+
+fn print_result(result: &DayResult) {
+
+    println!("The solution for part one is: {}", result.part_one);
+    println!("The solution for part two is: {}", result.part_two);
+    println!();
+
+    println!("Time breakdowns:");
+    println!("Setup: {:?}", result.timings.setup);
+    println!("Part 1: {:?}", result.timings.part_one);
+    println!("Part 2: {:?}", result.timings.part_two);
+    println!("Total: {:?}", result.timings.total());
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::get_inputs;
+    use crate::cli::Options;
+
+    fn sources(sources: &[&str]) -> Vec<String> {
+        sources.iter().map(|source| source.to_string()).collect()
+    }
+
+    #[test]
+    fn test_get_inputs_defaults_to_the_days_input() {
+        let inputs = get_inputs(1, &Options::default(), &[], "".as_bytes()).unwrap();
+
+        assert_eq!(inputs.len(), 1);
+        assert!(inputs[0].as_ref().unwrap().source.ends_with("input.txt"));
+    }
+
+    #[test]
+    fn test_get_inputs_reads_stdin_among_paths() {
+        let options = Options::default();
+        let day_one = options.inputs_dir.join("aoc01").join("input.txt");
+        let day_one = day_one.to_str().unwrap();
+
+        let inputs = get_inputs(1, &options, &sources(&[day_one, "-"]), "1721\n979\n".as_bytes()).unwrap();
+
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].as_ref().unwrap().source, day_one);
+        assert_eq!(inputs[1].as_ref().unwrap().source, "-");
+        assert_eq!(inputs[1].as_ref().unwrap().text, "1721\n979\n");
+    }
+
+    #[test]
+    fn test_get_inputs_reports_each_unreadable_input() {
+        let options = Options { inputs_dir: PathBuf::from("no/such/dir"), ..Options::default() };
+
+        let inputs = get_inputs(1, &options, &sources(&["missing.txt", "-"]), "5\n".as_bytes()).unwrap();
+        assert!(inputs[0].as_ref().unwrap_err().starts_with("could not read missing.txt"));
+        assert!(inputs[1].is_ok());
+
+        let inputs = get_inputs(1, &options, &[], "".as_bytes()).unwrap();
+        assert!(inputs[0].as_ref().unwrap_err().contains("no/such/dir"));
+    }
+
+    #[test]
+    fn test_get_inputs_reads_stdin_once() {
+        let error = get_inputs(1, &Options::default(), &sources(&["-", "-"]), "".as_bytes()).unwrap_err();
+
+        assert_eq!(error, "stdin can only be read once");
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+// What a set has to be able to do for the n-ary operations below.
+pub trait Set: Clone {
+    fn empty() -> Self;
+    fn union(&self, other: &Self) -> Self;
+    fn intersection(&self, other: &Self) -> Self;
+    fn symmetric_difference(&self, other: &Self) -> Self;
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Eq + Hash + Clone> Set for HashSet<T> {
+    fn empty() -> Self {
+        HashSet::new()
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        HashSet::union(self, other).cloned().collect()
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        HashSet::intersection(self, other).cloned().collect()
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        HashSet::symmetric_difference(self, other).cloned().collect()
+    }
+
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+}
+
+// The questions a to z that someone answered yes to, one bit each.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Answers(u32);
+
+impl Answers {
+    pub const ALL: Answers = Answers((1 << 26) - 1);
+
+    fn bit(question: char) -> Option<u32> {
+        match question {
+            'a'..='z' => Some(1 << (question as u32 - 'a' as u32)),
+            _ => None,
+        }
+    }
+
+    // Returns false for anything that isn't a question.
+    pub fn insert(&mut self, question: char) -> bool {
+        match Answers::bit(question) {
+            Some(bit) => {
+                self.0 |= bit;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, question: char) -> bool {
+        Answers::bit(question).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    pub fn questions(&self) -> impl Iterator<Item = char> {
+        let bits = self.0;
+        ('a'..='z').filter(move |&q| bits & Answers::bit(q).unwrap() != 0)
+    }
+}
+
+impl Set for Answers {
+    fn empty() -> Self {
+        Answers(0)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        Answers(self.0 | other.0)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        Answers(self.0 & other.0)
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        Answers(self.0 ^ other.0)
+    }
+
+    fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+}
+
+// Anything that isn't a question is left out.
+impl FromIterator<char> for Answers {
+    fn from_iter<I: IntoIterator<Item = char>>(questions: I) -> Self {
+        let mut answers = Answers::empty();
+        for question in questions {
+            answers.insert(question);
+        }
+        answers
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.questions().collect::<String>())
+    }
+}
+
+pub fn union<S: Set>(sets: &[S]) -> S {
+    sets.iter().fold(S::empty(), |acc, set| acc.union(set))
+}
+
+// Nothing is in every one of no sets.
+pub fn intersection<S: Set>(sets: &[S]) -> S {
+    match sets.split_first() {
+        Some((first, rest)) => rest.iter().fold(first.clone(), |acc, set| acc.intersection(set)),
+        None => S::empty(),
+    }
+}
+
+// Extended to more than two sets, this is what's in an odd number of them.
+pub fn symmetric_difference<S: Set>(sets: &[S]) -> S {
+    sets.iter().fold(S::empty(), |acc, set| acc.symmetric_difference(set))
+}
+
+// What's in at least k of the sets. levels[j] is what's been seen in at
+// least j + 1 of the sets so far, so each set can raise what was in j of
+// them to j + 1. Asking for at least 0 is the same as at least 1, as there's
+// no universe to take everything else from.
+pub fn at_least<S: Set>(sets: &[S], k: usize) -> S {
+
+    let k = k.max(1);
+    let mut levels = vec![S::empty(); k];
+
+    for set in sets {
+        for j in (1..k).rev() {
+            levels[j] = levels[j].union(&levels[j - 1].intersection(set));
+        }
+        levels[0] = levels[0].union(set);
+    }
+
+    levels.pop().unwrap()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{at_least, intersection, symmetric_difference, union, Answers, Set};
+
+    fn answers(groups: &[&str]) -> Vec<Answers> {
+        groups.iter().map(|group| group.chars().collect()).collect()
+    }
+
+    #[test]
+    fn test_answers() {
+        let mut set: Answers = "zab".chars().collect();
+
+        assert_eq!(set.to_string(), "abz");
+        assert_eq!(set.len(), 3);
+        assert!(set.contains('z'));
+        assert!(!set.insert('A'));
+        assert!(set.insert('c'));
+        assert_eq!(Answers::ALL.len(), 26);
+    }
+
+    #[test]
+    fn test_operations() {
+        let group = answers(&["abc", "abd", "ae"]);
+
+        assert_eq!(union(&group).to_string(), "abcde");
+        assert_eq!(intersection(&group).to_string(), "a");
+        assert_eq!(symmetric_difference(&group).to_string(), "acde");
+        assert_eq!(at_least(&group, 2).to_string(), "ab");
+        assert_eq!(at_least(&group, 3), intersection(&group));
+        assert_eq!(at_least(&group, 1), union(&group));
+        assert!(at_least(&group, 4).is_empty());
+    }
+
+    #[test]
+    fn test_empty_group() {
+        let group: Vec<Answers> = Vec::new();
+
+        assert!(union(&group).is_empty());
+        assert!(intersection(&group).is_empty());
+        assert!(symmetric_difference(&group).is_empty());
+        assert!(at_least(&group, 2).is_empty());
+    }
+
+    #[test]
+    fn test_hash_sets_agree() {
+        let group = answers(&["abcx", "bcy", "cxz", "q", "abcxyz"]);
+        let hash_sets: Vec<HashSet<char>> = group.iter().map(|a| a.questions().collect()).collect();
+        let sorted = |set: HashSet<char>| -> String {
+            let mut questions: Vec<char> = set.into_iter().collect();
+            questions.sort_unstable();
+            questions.into_iter().collect()
+        };
+
+        assert_eq!(sorted(union(&hash_sets)), union(&group).to_string());
+        assert_eq!(sorted(intersection(&hash_sets)), intersection(&group).to_string());
+        assert_eq!(sorted(symmetric_difference(&hash_sets)), symmetric_difference(&group).to_string());
+        for k in 0..=6 {
+            assert_eq!(sorted(at_least(&hash_sets, k)), at_least(&group, k).to_string());
+        }
+    }
+}
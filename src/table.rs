@@ -0,0 +1,76 @@
+// How many columns a cell takes up on screen, not counting any ANSI colour
+// codes in it.
+fn visible_width(cell: &str) -> usize {
+
+    let mut width = 0;
+    let mut in_escape = false;
+
+    for c in cell.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => width += 1,
+        }
+    }
+
+    width
+}
+
+// Lay out rows of cells as a plain text table, with each column as wide
+// as its widest cell.
+pub fn format_table(header: &[&str], rows: &[Vec<String>]) -> String {
+
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(visible_width(cell));
+        }
+    }
+
+    let format_row = |row: &[String]| -> String {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{}{}", cell, " ".repeat(width - visible_width(cell))))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+
+    let mut lines = vec![
+        format_row(&header),
+        widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>().join("-+-"),
+    ];
+    lines.extend(rows.iter().map(|row| format_row(row)));
+
+    lines.join("\n")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{format_table, visible_width};
+
+    #[test]
+    fn test_format_table() {
+        let rows = vec![
+            vec!["1".to_string(), "996996".to_string()],
+            vec!["12".to_string(), "381".to_string()],
+        ];
+
+        assert_eq!(format_table(&["Day", "Part 1"], &rows), "\
+Day | Part 1
+----+-------
+1   | 996996
+12  | 381");
+    }
+
+    #[test]
+    fn test_visible_width_ignores_colour() {
+        assert_eq!(visible_width("pass"), 4);
+        assert_eq!(visible_width("\x1b[32mpass\x1b[0m"), 4);
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+
+use toml::Value;
+
+use crate::runner::DayResult;
+use crate::table::format_table;
+
+// The recorded answers for a day. Either part can be left out if it isn't
+// known yet.
+#[derive(Debug, Default, PartialEq)]
+pub struct ExpectedAnswers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+pub fn answers_path(inputs_dir: &Path, day: u32) -> PathBuf {
+    inputs_dir.join(format!("aoc{:02}", day)).join("answers.toml")
+}
+
+// Each answer can be written as a string or an integer, and is kept as a
+// string to compare against the formatted results.
+fn answer(key: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(answer) => Ok(answer.clone()),
+        Value::Integer(answer) => Ok(answer.to_string()),
+        _ => Err(format!("{} should be a quoted string or an integer, not a {}", key, value.type_str())),
+    }
+}
+
+pub fn parse_answers(contents: &str) -> Result<ExpectedAnswers, String> {
+
+    let table = match toml::from_str(contents).map_err(|e| e.to_string())? {
+        Value::Table(table) => table,
+        _ => return Err("expected a table of answers".to_string()),
+    };
+
+    let mut answers = ExpectedAnswers::default();
+
+    for (key, value) in &table {
+        match key.as_str() {
+            "part_one" => answers.part_one = Some(answer(key, value)?),
+            "part_two" => answers.part_two = Some(answer(key, value)?),
+            _ => return Err(format!("unknown key '{}', expected part_one or part_two", key)),
+        }
+    }
+
+    Ok(answers)
+}
+
+// A missing file just means nothing has been recorded for that day yet.
+pub fn load_answers(path: &Path) -> Result<ExpectedAnswers, String> {
+
+    match fs::read_to_string(path) {
+        Ok(contents) => parse_answers(&contents)
+            .map_err(|e| format!("failed to parse {}\n{}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(ExpectedAnswers::default()),
+        Err(e) => Err(format!("could not read {}: {}", path.display(), e)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+    // The day couldn't be checked at all, because its input or answers
+    // couldn't be read or it failed to solve.
+    Error(String),
+}
+
+impl Status {
+    pub fn check(expected: &Option<String>, actual: &str) -> Self {
+        match expected {
+            None => Status::Missing,
+            Some(expected) if expected == actual => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.clone(),
+                actual: actual.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { .. } => write!(f, "FAIL"),
+            Status::Missing => write!(f, "missing"),
+            Status::Error(_) => write!(f, "ERROR"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DayVerification {
+    pub day: u32,
+    pub part_one: Status,
+    pub part_two: Status,
+}
+
+pub fn verify(result: &DayResult, expected: &ExpectedAnswers) -> DayVerification {
+    DayVerification {
+        day: result.day,
+        part_one: Status::check(&expected.part_one, &result.part_one),
+        part_two: Status::check(&expected.part_two, &result.part_two),
+    }
+}
+
+pub fn failed(day: u32, error: String) -> DayVerification {
+    DayVerification {
+        day,
+        part_one: Status::Error(error.clone()),
+        part_two: Status::Error(error),
+    }
+}
+
+fn colour(status: &Status, use_colour: bool) -> String {
+
+    if !use_colour {
+        return status.to_string()
+    }
+
+    let code = match status {
+        Status::Pass => "32",
+        Status::Fail { .. } | Status::Error(_) => "31",
+        Status::Missing => "33",
+    };
+
+    format!("\x1b[{}m{}\x1b[0m", code, status)
+}
+
+// Print the pass/fail matrix, then the details of anything that failed.
+// Returns whether every day could be checked and everything that had a
+// recorded answer matched it.
+pub fn report(verifications: &[DayVerification]) -> bool {
+
+    let use_colour = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+
+    let rows: Vec<Vec<String>> = verifications.iter()
+        .map(|v| vec![
+            v.day.to_string(),
+            colour(&v.part_one, use_colour),
+            colour(&v.part_two, use_colour),
+        ])
+        .collect();
+
+    println!("{}", format_table(&["Day", "Part 1", "Part 2"], &rows));
+
+    let mut all_passed = true;
+    for v in verifications {
+        let mut details = Vec::new();
+        for (part, status) in [(1, &v.part_one), (2, &v.part_two)].iter() {
+            match status {
+                Status::Fail { expected, actual } => {
+                    details.push(format!("Day {} part {}: expected {} but got {}", v.day, part, expected, actual));
+                }
+                Status::Error(error) => details.push(format!("Day {}: {}", v.day, error)),
+                _ => {}
+            }
+        }
+
+        // A day that failed outright has the same error for both parts.
+        details.dedup();
+
+        for line in details {
+            if all_passed {
+                println!();
+            }
+            all_passed = false;
+            println!("{}", line);
+        }
+    }
+
+    all_passed
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{failed, parse_answers, ExpectedAnswers, Status};
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("# day 3\npart_one = 162\n\npart_two = \"3064612320\" # big\n").unwrap();
+
+        assert_eq!(answers, ExpectedAnswers {
+            part_one: Some("162".to_string()),
+            part_two: Some("3064612320".to_string()),
+        });
+
+        assert_eq!(parse_answers("part_one = 1").unwrap().part_two, None);
+    }
+
+    #[test]
+    fn test_parse_answers_keeps_quoted_hashes() {
+        let answers = parse_answers("part_one = \"a#b\" # not part of it\npart_two = 'c#d'").unwrap();
+
+        assert_eq!(answers.part_one, Some("a#b".to_string()));
+        assert_eq!(answers.part_two, Some("c#d".to_string()));
+    }
+
+    #[test]
+    fn test_parse_answers_errors() {
+        assert_eq!(parse_answers("part_one = 1\npart_three = 2"), Err("unknown key 'part_three', expected part_one or part_two".to_string()));
+        assert_eq!(parse_answers("part_one = 1.5"), Err("part_one should be a quoted string or an integer, not a float".to_string()));
+        assert!(parse_answers("part_one = \"1").is_err());
+        assert!(parse_answers("part_one 1").is_err());
+        assert!(parse_answers("part_one = one").is_err());
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(Status::check(&Some("5".to_string()), "5"), Status::Pass);
+        assert_eq!(Status::check(&None, "5"), Status::Missing);
+        assert_eq!(Status::check(&Some("5".to_string()), "6"), Status::Fail {
+            expected: "5".to_string(),
+            actual: "6".to_string(),
+        });
+    }
+
+    #[test]
+    fn test_failed_day() {
+        let verification = failed(4, "could not read inputs/aoc04/input".to_string());
+
+        assert_eq!(verification.day, 4);
+        assert_eq!(verification.part_two, Status::Error("could not read inputs/aoc04/input".to_string()));
+        assert_eq!(verification.part_one.to_string(), "ERROR");
+    }
+}